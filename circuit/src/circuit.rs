@@ -1,17 +1,120 @@
 use ark_ff::PrimeField;
 use ark_bn254::Fr;
+use rand::Rng;
 use std::marker::PhantomData;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CIRCUIT_OP{
   ADD,
-  MUL
+  MUL,
+  SUB
 }
 
 #[derive(Debug, Clone)]
 pub enum Gate {
     Add(usize, usize), // Indexes of the values to add
     Mul(usize, usize), // Indexes of the values to multiply
+    Sub(usize, usize), // Indexes of the minuend and the subtrahend
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CircuitError {
+    // A gate in `layer` reads wire `index`, which is neither an input nor produced by an earlier layer
+    BadWire { layer: usize, index: usize },
+    // `evaluate` was given `got` inputs but the gates read exactly `expected`
+    InputCountMismatch { expected: usize, got: usize },
+    // `output_value` was called on a circuit whose last layer has `outputs` gates instead of one
+    NotSingleOutput { outputs: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnknownVariable(String),
+    // a name in `var_names` that the expression never reads, which would leave the circuit taking fewer
+    // inputs than were declared
+    UnusedVariable(String),
+    UnexpectedCharacter(char),
+    UnbalancedParentheses,
+    // an operator without two operands, or operands without an operator between them
+    MalformedExpression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Var(usize),
+    Op(CIRCUIT_OP),
+    LeftParen,
+    RightParen,
+}
+
+fn tokenize(expr: &str, var_names: &[&str]) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = vec![];
+    let mut chars = expr.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' | '\n' => { chars.next(); }
+            '+' => { chars.next(); tokens.push(Token::Op(CIRCUIT_OP::ADD)); }
+            '*' => { chars.next(); tokens.push(Token::Op(CIRCUIT_OP::MUL)); }
+            '(' => { chars.next(); tokens.push(Token::LeftParen); }
+            ')' => { chars.next(); tokens.push(Token::RightParen); }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    name.push(c);
+                    chars.next();
+                }
+                let index = var_names.iter().position(|v| *v == name).ok_or(ParseError::UnknownVariable(name))?;
+                tokens.push(Token::Var(index));
+            }
+            c => return Err(ParseError::UnexpectedCharacter(c)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn precedence(op: &CIRCUIT_OP) -> u8 {
+    match op {
+        CIRCUIT_OP::ADD | CIRCUIT_OP::SUB => 0,
+        CIRCUIT_OP::MUL => 1,
+    }
+}
+
+// a gate of `from_expression` whose operands are still (layer, position) pairs
+type PendingGate = (CIRCUIT_OP, (usize, usize), (usize, usize));
+
+// Shunting-yard: infix tokens to postfix, with MUL binding tighter than ADD and both left associative
+fn to_postfix(tokens: Vec<Token>) -> Result<Vec<Token>, ParseError> {
+    let mut output = vec![];
+    let mut operators: Vec<Token> = vec![];
+    for token in tokens {
+        match token {
+            Token::Var(_) => output.push(token),
+            Token::Op(ref op) => {
+                while let Some(Token::Op(top)) = operators.last() {
+                    if precedence(top) < precedence(op) {
+                        break;
+                    }
+                    output.push(operators.pop().unwrap());
+                }
+                operators.push(token);
+            }
+            Token::LeftParen => operators.push(token),
+            Token::RightParen => loop {
+                match operators.pop() {
+                    Some(Token::LeftParen) => break,
+                    Some(op) => output.push(op),
+                    None => return Err(ParseError::UnbalancedParentheses),
+                }
+            },
+        }
+    }
+    while let Some(token) = operators.pop() {
+        if token == Token::LeftParen {
+            return Err(ParseError::UnbalancedParentheses);
+        }
+        output.push(token);
+    }
+    Ok(output)
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +135,183 @@ impl<F: PrimeField> Circuit<F> {
         self.layers.push(layer);
     }
 
+    // A circuit over `num_inputs` inputs with one layer of `layer_widths[k]` gates per entry. Every gate picks
+    // its op and both operands uniformly, reading any input or any gate of an earlier layer, so the result
+    // always passes `check_topological_order`.
+    pub fn random<R: Rng>(num_inputs: usize, layer_widths: &[usize], rng: &mut R) -> Self {
+        let mut circuit = Circuit::new();
+        let mut available = num_inputs;
+        for &width in layer_widths {
+            let layer = (0..width)
+                .map(|_| {
+                    let (a, b) = (rng.random_range(0..available), rng.random_range(0..available));
+                    match rng.random_range(0..3) {
+                        0 => Gate::Add(a, b),
+                        1 => Gate::Mul(a, b),
+                        _ => Gate::Sub(a, b),
+                    }
+                })
+                .collect();
+            circuit.add_layer(layer);
+            available += width;
+        }
+        circuit
+    }
+
+    // Like `random`, but in the shape GKR proves: only Add and Mul gates, each reading two wires of the
+    // layer directly below it
+    pub fn random_layered<R: Rng>(num_inputs: usize, layer_widths: &[usize], rng: &mut R) -> Self {
+        let mut circuit = Circuit::new();
+        let (mut below_start, mut below_len) = (0, num_inputs);
+        for &width in layer_widths {
+            let layer = (0..width)
+                .map(|_| {
+                    let a = below_start + rng.random_range(0..below_len);
+                    let b = below_start + rng.random_range(0..below_len);
+                    if rng.random_bool(0.5) { Gate::Add(a, b) } else { Gate::Mul(a, b) }
+                })
+                .collect();
+            circuit.add_layer(layer);
+            below_start += below_len;
+            below_len = width;
+        }
+        circuit
+    }
+
+    // Layers are stored from the inputs up: `layers[0]` reads the inputs and the last layer is the output.
+    // GKR walks the other way, with layer 0 as the output, so this lists the layers in the order it
+    // consumes them.
+    pub fn output_to_input_layers(&self) -> Vec<&Vec<Gate>> {
+        self.layers.iter().rev().collect()
+    }
+
+    // The layers in the order they were added, from the one reading the inputs up to the output layer
+    pub fn layers(&self) -> &[Vec<Gate>] {
+        &self.layers
+    }
+
+    // Builds the circuit of an infix expression over `var_names`, e.g. "(x0 + x1) * x2", whose inputs are
+    // the variables in `var_names` order. Each gate goes one layer above the deeper of its operands, so the
+    // last layer holds the single output gate. Every declared variable has to appear in the expression.
+    pub fn from_expression(expr: &str, var_names: &[&str]) -> Result<Self, ParseError> {
+        let tokens = tokenize(expr, var_names)?;
+        let unused = (0..var_names.len()).find(|index| !tokens.contains(&Token::Var(*index)));
+        let postfix = to_postfix(tokens)?;
+
+        // (layer, position) of every operand on the stack; inputs are layer 0
+        let mut stack: Vec<(usize, usize)> = vec![];
+        let mut layers: Vec<Vec<PendingGate>> = vec![];
+        for token in postfix {
+            match token {
+                Token::Var(index) => stack.push((0, index)),
+                Token::Op(op) => {
+                    let right = stack.pop().ok_or(ParseError::MalformedExpression)?;
+                    let left = stack.pop().ok_or(ParseError::MalformedExpression)?;
+                    let layer = left.0.max(right.0) + 1;
+                    if layers.len() < layer {
+                        layers.push(vec![]);
+                    }
+                    layers[layer - 1].push((op, left, right));
+                    stack.push((layer, layers[layer - 1].len() - 1));
+                }
+                _ => unreachable!("parentheses are removed by to_postfix"),
+            }
+        }
+        if stack.len() != 1 {
+            return Err(ParseError::MalformedExpression);
+        }
+        if let Some(index) = unused {
+            return Err(ParseError::UnusedVariable(var_names[index].to_string()));
+        }
+        Ok(Self::from_pending_layers(var_names.len(), layers))
+    }
+
+    // Resolves the (layer, position) operands of every pending gate to wire indices, layer 0 being the inputs
+    fn from_pending_layers(num_inputs: usize, layers: Vec<Vec<PendingGate>>) -> Self {
+        // wire index of the first gate of every layer: inputs first, then the layers in order
+        let mut offsets = vec![num_inputs];
+        for layer in layers.iter() {
+            offsets.push(offsets[offsets.len() - 1] + layer.len());
+        }
+        let wire = |(layer, position): (usize, usize)| if layer == 0 { position } else { offsets[layer - 1] + position };
+
+        let mut circuit = Circuit::new();
+        for layer in layers {
+            circuit.add_layer(
+                layer
+                    .into_iter()
+                    .map(|(op, left, right)| match op {
+                        CIRCUIT_OP::ADD => Gate::Add(wire(left), wire(right)),
+                        CIRCUIT_OP::MUL => Gate::Mul(wire(left), wire(right)),
+                        CIRCUIT_OP::SUB => Gate::Sub(wire(left), wire(right)),
+                    })
+                    .collect(),
+            );
+        }
+        circuit
+    }
+
+    // Checks that every gate only reads inputs or values produced by earlier layers. Wires are numbered
+    // inputs first, then each layer's outputs in order, so layer k can read indices below
+    // num_inputs + (gates in layers 0..k).
+    pub fn check_topological_order(&self, num_inputs: usize) -> Result<(), CircuitError> {
+        let mut available = num_inputs;
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            for gate in layer {
+                let (a, b) = match gate {
+                    Gate::Add(a, b) | Gate::Mul(a, b) | Gate::Sub(a, b) => (*a, *b),
+                };
+                for index in [a, b] {
+                    if index >= available {
+                        return Err(CircuitError::BadWire { layer: layer_index, index });
+                    }
+                }
+            }
+            available += layer.len();
+        }
+        Ok(())
+    }
+
+    // Well-formedness check to run before evaluating with `num_inputs` inputs; the same walk as
+    // `check_topological_order`
+    pub fn validate(&self, num_inputs: usize) -> Result<(), CircuitError> {
+        self.check_topological_order(num_inputs)
+    }
+
+    // Smallest number of inputs that lets every gate read an existing wire: layer k reads indices below
+    // num_inputs + (gates in layers 0..k), so each of its references bounds num_inputs from below
+    pub fn inferred_num_inputs(&self) -> usize {
+        let mut gates_before = 0;
+        let mut num_inputs = 0;
+        for layer in &self.layers {
+            for gate in layer {
+                let (a, b) = match gate {
+                    Gate::Add(a, b) | Gate::Mul(a, b) | Gate::Sub(a, b) => (*a, *b),
+                };
+                num_inputs = num_inputs.max((a.max(b) + 1).saturating_sub(gates_before));
+            }
+            gates_before += layer.len();
+        }
+        num_inputs
+    }
+
+    // `evaluate` that rejects an input vector whose length differs from `inferred_num_inputs`: too few
+    // would index out of bounds, and extra inputs would shift every later layer's wire indices
+    pub fn try_evaluate(&self, inputs: Vec<F>) -> Result<Vec<Vec<F>>, CircuitError> {
+        let expected = self.inferred_num_inputs();
+        if inputs.len() != expected {
+            return Err(CircuitError::InputCountMismatch { expected, got: inputs.len() });
+        }
+        Ok(self.evaluate(inputs))
+    }
+
+    // `evaluate` that validates the wiring against `inputs.len()` first, returning `BadWire` where
+    // `evaluate` would index out of bounds
+    pub fn evaluate_validated(&self, inputs: Vec<F>) -> Result<Vec<Vec<F>>, CircuitError> {
+        self.validate(inputs.len())?;
+        Ok(self.evaluate(inputs))
+    }
+
     pub fn evaluate(&self, inputs: Vec<F>) -> Vec<Vec<F>> {
         let mut evaluation_steps = vec![inputs.clone()];
         let mut all_values = inputs; // Contains all values: inputs + intermediate results
@@ -43,6 +323,7 @@ impl<F: PrimeField> Circuit<F> {
                 let result = match gate {
                     Gate::Add(a, b) => all_values[*a] + all_values[*b],
                     Gate::Mul(a, b) => all_values[*a] * all_values[*b],
+                    Gate::Sub(a, b) => all_values[*a] - all_values[*b],
                 };
                 new_values.push(result);
             }
@@ -54,6 +335,25 @@ impl<F: PrimeField> Circuit<F> {
         evaluation_steps
     }
 
+    // Just the last evaluation step; for a circuit with no layers that's the inputs themselves
+    pub fn output(&self, inputs: Vec<F>) -> Vec<F> {
+        self.evaluate(inputs).pop().unwrap()
+    }
+
+    // The value a single-output circuit computes, through `try_evaluate` so a wrong input count is an error too
+    pub fn output_value(&self, inputs: &[F]) -> Result<F, CircuitError> {
+        let outputs = self.output_len();
+        if outputs != 1 {
+            return Err(CircuitError::NotSingleOutput { outputs });
+        }
+        Ok(self.try_evaluate(inputs.to_vec())?.pop().unwrap()[0])
+    }
+
+    // Length of `output`, read off the last layer's gate count
+    pub fn output_len(&self) -> usize {
+        self.layers.last().map_or_else(|| self.inferred_num_inputs(), Vec::len)
+    }
+
     pub fn get_layer_evaluation(&self, inputs: Vec<F>, layer_index: usize) -> Option<Vec<F>> {
         let evaluation_steps = self.evaluate(inputs);
         if layer_index < evaluation_steps.len() {
@@ -63,49 +363,108 @@ impl<F: PrimeField> Circuit<F> {
         }
     }
 
-    pub fn addi(&self, layer_index: usize, all_values: &Vec<F>) -> Option<Vec<F>> {
+    // Values of the gates of `layer_index` that match `gate_type`, or None if a gate reads past `all_values`
+    fn gate_values(&self, layer_index: usize, all_values: &[F], gate_type: CIRCUIT_OP) -> Option<Vec<F>> {
         if layer_index >= self.layers.len() {
             return None;
         }
-    
+
         let mut results = Vec::new();
         for gate in &self.layers[layer_index] {
-            if let Gate::Add(a, b) = gate {
-                // Check if indices are within bounds
-                if *a >= all_values.len() || *b >= all_values.len() {
-                    return None; // Return None if indices are out of bounds
-                }
-                results.push(all_values[*a] + all_values[*b]);
+            let (a, b) = match (gate, &gate_type) {
+                (Gate::Add(a, b), CIRCUIT_OP::ADD)
+                | (Gate::Mul(a, b), CIRCUIT_OP::MUL)
+                | (Gate::Sub(a, b), CIRCUIT_OP::SUB) => (*a, *b),
+                _ => continue,
+            };
+            // Check if indices are within bounds
+            if a >= all_values.len() || b >= all_values.len() {
+                return None; // Return None if indices are out of bounds
             }
+            results.push(match gate_type {
+                CIRCUIT_OP::ADD => all_values[a] + all_values[b],
+                CIRCUIT_OP::MUL => all_values[a] * all_values[b],
+                CIRCUIT_OP::SUB => all_values[a] - all_values[b],
+            });
         }
         Some(results)
     }
 
+    pub fn addi(&self, layer_index: usize, all_values: &Vec<F>) -> Option<Vec<F>> {
+        self.gate_values(layer_index, all_values, CIRCUIT_OP::ADD)
+    }
+
     pub fn muli(&self, layer_index: usize, all_values: &Vec<F>) -> Option<Vec<F>> {
-        if layer_index >= self.layers.len() {
-            return None;
-        }
-    
-        let mut results = Vec::new();
-        for gate in &self.layers[layer_index] {
-            if let Gate::Mul(a, b) = gate {
-                // Check if indices are within bounds
-                if *a >= all_values.len() || *b >= all_values.len() {
-                    return None; // Return None if indices are out of bounds
-                }
-                results.push(all_values[*a] * all_values[*b]);
-            }
-        }
-    
         // Return None if there are no Mul gates in the layer
-        if results.is_empty() {
-            None
-        } else {
-            Some(results)
+        self.gate_values(layer_index, all_values, CIRCUIT_OP::MUL)
+            .filter(|results| !results.is_empty())
+    }
+
+    pub fn subi(&self, layer_index: usize, all_values: &[F]) -> Option<Vec<F>> {
+        self.gate_values(layer_index, all_values, CIRCUIT_OP::SUB)
+    }
+
+}
+
+// A wire handed out by `CircuitBuilder`: an input or the output of a gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireId(usize);
+
+// Builds a circuit from wires instead of raw value indices. Each gate goes one layer above the deeper of its
+// operands, so gates are grouped into layers by dependency depth, and `build` works out the indices.
+#[derive(Debug, Clone)]
+pub struct CircuitBuilder<F: PrimeField> {
+    // (layer, position) of every wire handed out, indexed by `WireId`; inputs are layer 0
+    wires: Vec<(usize, usize)>,
+    num_inputs: usize,
+    layers: Vec<Vec<PendingGate>>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField> CircuitBuilder<F> {
+    pub fn new() -> Self {
+        Self { wires: vec![], num_inputs: 0, layers: vec![], _marker: PhantomData }
+    }
+
+    // The next input; inputs are numbered in the order they're requested
+    pub fn input(&mut self) -> WireId {
+        self.wires.push((0, self.num_inputs));
+        self.num_inputs += 1;
+        WireId(self.wires.len() - 1)
+    }
+
+    pub fn add(&mut self, a: WireId, b: WireId) -> WireId {
+        self.gate(CIRCUIT_OP::ADD, a, b)
+    }
+
+    pub fn mul(&mut self, a: WireId, b: WireId) -> WireId {
+        self.gate(CIRCUIT_OP::MUL, a, b)
+    }
+
+    pub fn sub(&mut self, a: WireId, b: WireId) -> WireId {
+        self.gate(CIRCUIT_OP::SUB, a, b)
+    }
+
+    fn gate(&mut self, op: CIRCUIT_OP, a: WireId, b: WireId) -> WireId {
+        let (left, right) = (self.wires[a.0], self.wires[b.0]);
+        let layer = left.0.max(right.0) + 1;
+        if self.layers.len() < layer {
+            self.layers.push(vec![]);
         }
+        self.layers[layer - 1].push((op, left, right));
+        self.wires.push((layer, self.layers[layer - 1].len() - 1));
+        WireId(self.wires.len() - 1)
     }
 
+    pub fn build(self) -> Circuit<F> {
+        Circuit::from_pending_layers(self.num_inputs, self.layers)
+    }
+}
 
+impl<F: PrimeField> Default for CircuitBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 fn main() {
@@ -115,6 +474,49 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    // Reference for `evaluate`: names every wire explicitly, inputs first and then each gate in order
+    fn reference_evaluate(circuit: &Circuit<Fr>, inputs: &[Fr]) -> Vec<Vec<Fr>> {
+        let mut wires: HashMap<usize, Fr> = HashMap::new();
+        for (i, input) in inputs.iter().enumerate() {
+            wires.insert(i, *input);
+        }
+        let mut steps = vec![inputs.to_vec()];
+        for layer in &circuit.layers {
+            let mut outputs = vec![];
+            for gate in layer {
+                let value = match gate {
+                    Gate::Add(a, b) => wires[a] + wires[b],
+                    Gate::Mul(a, b) => wires[a] * wires[b],
+                    Gate::Sub(a, b) => wires[a] - wires[b],
+                };
+                outputs.push(value);
+            }
+            // a layer's gates only read earlier wires, so its outputs are named once all are computed
+            for value in &outputs {
+                wires.insert(wires.len(), *value);
+            }
+            steps.push(outputs);
+        }
+        steps
+    }
+
+    #[test]
+    fn test_evaluate_matches_reference_on_random_circuits() {
+        let mut rng = StdRng::seed_from_u64(2275);
+        for _ in 0..300 {
+            let num_inputs = rng.random_range(1..8);
+            let layer_widths: Vec<usize> = (0..rng.random_range(1..5)).map(|_| rng.random_range(1..6)).collect();
+            let circuit = Circuit::<Fr>::random(num_inputs, &layer_widths, &mut rng);
+            assert_eq!(circuit.check_topological_order(num_inputs), Ok(()));
+
+            let inputs: Vec<Fr> = (0..num_inputs).map(|_| Fr::from(rng.random::<u64>())).collect();
+            assert_eq!(circuit.evaluate(inputs.clone()), reference_evaluate(&circuit, &inputs), "{:?}", circuit);
+        }
+    }
 
     #[test]
     fn test_circuit_evaluation() {
@@ -140,6 +542,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sub_gate() {
+        let (a, b, c, d) = (Fr::from(10), Fr::from(7), Fr::from(3), Fr::from(4));
+
+        let mut circuit = Circuit::new();
+        // Layer 1: [10,7,3,4] -> [10+7=17, 3*4=12]
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        // Layer 2: [17,12] -> [17-12=5]
+        circuit.add_layer(vec![Gate::Sub(4, 5)]);
+
+        let inputs = vec![a, b, c, d];
+        let evaluation = circuit.evaluate(inputs.clone());
+        assert_eq!(evaluation[2], vec![(a + b) - (c * d)]);
+        assert_eq!(evaluation[2], vec![Fr::from(5)]);
+        assert_eq!(circuit.get_layer_evaluation(inputs.clone(), 2), Some(vec![Fr::from(5)]));
+        assert_eq!(circuit.check_topological_order(4), Ok(()));
+
+        // the result wraps around the field when the subtrahend is larger
+        let swapped = circuit.evaluate(vec![c, d, a, b]);
+        assert_eq!(swapped[2], vec![-Fr::from(63)]);
+
+        let all_values: Vec<Fr> = evaluation[..2].concat();
+        assert_eq!(circuit.subi(1, &all_values), Some(vec![Fr::from(5)]));
+        assert_eq!(circuit.subi(0, &inputs), Some(vec![]));
+        assert_eq!(circuit.addi(1, &all_values), Some(vec![]));
+    }
+
+    #[test]
+    fn test_output_to_input_layers() {
+        let mut circuit: Circuit<Fr> = Circuit::new();
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        circuit.add_layer(vec![Gate::Add(4, 5)]);
+
+        let layers = circuit.output_to_input_layers();
+        assert_eq!(layers.len(), 2);
+        // the output gate comes first, the gates reading the inputs last
+        assert!(matches!(layers[0][..], [Gate::Add(4, 5)]));
+        assert!(matches!(layers[1][..], [Gate::Add(0, 1), Gate::Mul(2, 3)]));
+
+        // the same order as the evaluation steps read from the output back to the inputs
+        let evaluation = circuit.evaluate(vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)]);
+        for (i, layer) in layers.iter().enumerate() {
+            assert_eq!(layer.len(), evaluation[evaluation.len() - 1 - i].len());
+        }
+    }
+
     #[test]
     fn test_circuit_evaluation_with_more_inputs() {
         let input1 = Fr::from(1);
@@ -190,6 +638,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_builder_matches_hand_built_circuit() {
+        let inputs: Vec<Fr> = (1..=8).map(Fr::from).collect();
+
+        let mut hand_built = Circuit::new();
+        hand_built.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        hand_built.add_layer(vec![Gate::Add(4, 5), Gate::Mul(6, 7)]);
+        hand_built.add_layer(vec![Gate::Add(8, 9), Gate::Mul(10, 11)]);
+        hand_built.add_layer(vec![Gate::Add(12, 13)]);
+
+        let mut builder = CircuitBuilder::<Fr>::new();
+        let x: Vec<WireId> = (0..8).map(|_| builder.input()).collect();
+        let (s0, p0) = (builder.add(x[0], x[1]), builder.mul(x[2], x[3]));
+        let (s1, p1) = (builder.add(x[4], x[5]), builder.mul(x[6], x[7]));
+        let (s2, p2) = (builder.add(s0, p0), builder.mul(s1, p1));
+        builder.add(s2, p2);
+        let built = builder.build();
+
+        // the second layer of hand-built gates only reads inputs, so the builder lifts it into the first
+        assert_eq!(built.layers.len(), 3);
+        assert_eq!(built.check_topological_order(8), Ok(()));
+        let evaluation = built.evaluate(inputs.clone());
+        assert_eq!(evaluation.last(), hand_built.evaluate(inputs.clone()).last());
+        assert_eq!(evaluation.last().unwrap(), &vec![Fr::from(15 + 11 * 56)]);
+
+        // the builder agrees with from_expression, which groups by depth the same way
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let parsed = Circuit::<Fr>::from_expression("(a + b) + c * d + (e + f) * (g * h)", &names).unwrap();
+        assert_eq!(parsed.evaluate(inputs.clone()), evaluation);
+    }
+
     #[test]
     fn test_circuit_evaluation_one() {
         let input1 = Fr::from(1);
@@ -240,6 +719,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_output() {
+        let inputs: Vec<Fr> = (1..=8).map(Fr::from).collect();
+        let mut circuit = Circuit::new();
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3), Gate::Mul(4, 5), Gate::Add(6, 7)]);
+        circuit.add_layer(vec![Gate::Add(8, 9), Gate::Mul(10, 11)]);
+
+        let output = circuit.output(inputs.clone());
+        assert_eq!(&output, circuit.evaluate(inputs.clone()).last().unwrap());
+        assert_eq!(output, vec![Fr::from(3 + 12), Fr::from(30 * 15)]);
+        assert_eq!(circuit.output_len(), 2);
+
+        circuit.add_layer(vec![Gate::Sub(12, 13)]);
+        assert_eq!(circuit.output_len(), 1);
+        assert_eq!(circuit.output(inputs), vec![Fr::from(15) - Fr::from(450)]);
+    }
+
+    #[test]
+    fn test_output_value() {
+        let inputs: Vec<Fr> = (1..=8).map(Fr::from).collect();
+        let mut circuit = Circuit::new();
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        circuit.add_layer(vec![Gate::Add(4, 5), Gate::Mul(6, 7)]);
+        circuit.add_layer(vec![Gate::Add(8, 9), Gate::Mul(10, 11)]);
+
+        assert_eq!(circuit.output_value(&inputs), Err(CircuitError::NotSingleOutput { outputs: 2 }));
+
+        circuit.add_layer(vec![Gate::Add(12, 13)]);
+        // (3 + 12) + (11 * 56)
+        assert_eq!(circuit.output_value(&inputs), Ok(Fr::from(631)));
+        assert_eq!(
+            circuit.output_value(&inputs[..7]),
+            Err(CircuitError::InputCountMismatch { expected: 8, got: 7 })
+        );
+    }
+
     #[test]
     fn test_get_layer_evaluation() {
         let input1 = Fr::from(1);
@@ -324,6 +839,131 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_check_topological_order() {
+        let mut circuit = Circuit::<Fr>::new();
+        // Layer 1: [1,2,3,4,5,6,7,8] -> [1+2=3, 3*4=12]
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        // Layer 2: reads the remaining inputs
+        circuit.add_layer(vec![Gate::Add(4, 5), Gate::Mul(6, 7)]);
+        // Layer 3: reads both earlier layers
+        circuit.add_layer(vec![Gate::Add(8, 9), Gate::Mul(10, 11)]);
+        circuit.add_layer(vec![Gate::Add(12, 13)]);
+
+        assert_eq!(circuit.check_topological_order(8), Ok(()));
+    }
+
+    #[test]
+    fn test_validate() {
+        let inputs: Vec<Fr> = (1..=8).map(Fr::from).collect();
+        let mut circuit = Circuit::<Fr>::new();
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3), Gate::Mul(4, 5), Gate::Add(6, 7)]);
+        circuit.add_layer(vec![Gate::Add(8, 9), Gate::Sub(10, 11)]);
+        circuit.add_layer(vec![Gate::Mul(12, 13)]);
+
+        assert_eq!(circuit.validate(8), Ok(()));
+        assert_eq!(circuit.evaluate_validated(inputs.clone()), Ok(circuit.evaluate(inputs.clone())));
+
+        // index 14 is the output of this same layer
+        let mut forward = Circuit::<Fr>::new();
+        forward.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3), Gate::Mul(4, 5), Gate::Add(6, 7)]);
+        forward.add_layer(vec![Gate::Add(8, 9), Gate::Sub(10, 11)]);
+        forward.add_layer(vec![Gate::Mul(12, 14)]);
+
+        assert_eq!(forward.validate(8), Err(CircuitError::BadWire { layer: 2, index: 14 }));
+        assert_eq!(forward.evaluate_validated(inputs), Err(CircuitError::BadWire { layer: 2, index: 14 }));
+    }
+
+    #[test]
+    fn test_check_topological_order_rejects_forward_reference() {
+        let mut circuit = Circuit::<Fr>::new();
+        // index 5 is the second output of this same layer, which doesn't exist yet
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 5)]);
+        circuit.add_layer(vec![Gate::Add(4, 5)]);
+
+        assert_eq!(
+            circuit.check_topological_order(4),
+            Err(CircuitError::BadWire { layer: 0, index: 5 })
+        );
+
+        let mut circuit = Circuit::<Fr>::new();
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        // index 6 would be produced by this layer
+        circuit.add_layer(vec![Gate::Add(4, 6)]);
+
+        assert_eq!(
+            circuit.check_topological_order(4),
+            Err(CircuitError::BadWire { layer: 1, index: 6 })
+        );
+    }
+
+    #[test]
+    fn test_try_evaluate_checks_input_count() {
+        let mut circuit = Circuit::<Fr>::new();
+        circuit.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        circuit.add_layer(vec![Gate::Add(4, 5), Gate::Mul(6, 7)]);
+        circuit.add_layer(vec![Gate::Add(8, 9), Gate::Mul(10, 11)]);
+        circuit.add_layer(vec![Gate::Add(12, 13)]);
+        assert_eq!(circuit.inferred_num_inputs(), 8);
+
+        let inputs: Vec<Fr> = (1..=8).map(Fr::from).collect();
+        assert_eq!(circuit.try_evaluate(inputs.clone()), Ok(circuit.evaluate(inputs.clone())));
+
+        assert_eq!(
+            circuit.try_evaluate(inputs[..6].to_vec()),
+            Err(CircuitError::InputCountMismatch { expected: 8, got: 6 })
+        );
+
+        let mut too_many = inputs;
+        too_many.push(Fr::from(9));
+        assert_eq!(
+            circuit.try_evaluate(too_many),
+            Err(CircuitError::InputCountMismatch { expected: 8, got: 9 })
+        );
+    }
+
+    #[test]
+    fn test_from_expression() {
+        let names = ["x0", "x1", "x2", "x3"];
+        let inputs: Vec<Fr> = [3, 5, 7, 11].iter().map(|x| Fr::from(*x)).collect();
+
+        let parsed = Circuit::<Fr>::from_expression("(x0 + x1) * (x2 * x3)", &names).unwrap();
+        let mut hand_built = Circuit::new();
+        hand_built.add_layer(vec![Gate::Add(0, 1), Gate::Mul(2, 3)]);
+        hand_built.add_layer(vec![Gate::Mul(4, 5)]);
+
+        let result = parsed.evaluate(inputs.clone());
+        assert_eq!(result, hand_built.evaluate(inputs.clone()));
+        assert_eq!(result.last().unwrap(), &vec![Fr::from(8 * 77)]);
+
+        // precedence and a gate reading an input and a deeper gate
+        let parsed = Circuit::<Fr>::from_expression("x0 + x1 * x2 * x3", &names).unwrap();
+        assert_eq!(parsed.check_topological_order(4), Ok(()));
+        assert_eq!(parsed.evaluate(inputs).last().unwrap(), &vec![Fr::from(3 + 385)]);
+    }
+
+    #[test]
+    fn test_from_expression_errors() {
+        let names = ["a", "b"];
+        assert_eq!(Circuit::<Fr>::from_expression("a + c", &names).unwrap_err(), ParseError::UnknownVariable("c".to_string()));
+        assert_eq!(Circuit::<Fr>::from_expression("a - b", &names).unwrap_err(), ParseError::UnexpectedCharacter('-'));
+        assert_eq!(Circuit::<Fr>::from_expression("(a + b", &names).unwrap_err(), ParseError::UnbalancedParentheses);
+        assert_eq!(Circuit::<Fr>::from_expression("a + b)", &names).unwrap_err(), ParseError::UnbalancedParentheses);
+        assert_eq!(Circuit::<Fr>::from_expression("a +", &names).unwrap_err(), ParseError::MalformedExpression);
+        assert_eq!(Circuit::<Fr>::from_expression("a b", &names).unwrap_err(), ParseError::MalformedExpression);
+    }
+
+    #[test]
+    fn test_from_expression_rejects_unused_variables() {
+        // an unused trailing variable would otherwise leave output_value rejecting the declared inputs
+        let names = ["x0", "x1", "x2"];
+        assert_eq!(Circuit::<Fr>::from_expression("x0 + x1", &names).unwrap_err(), ParseError::UnusedVariable("x2".to_string()));
+        assert_eq!(Circuit::<Fr>::from_expression("x0 * x2", &names).unwrap_err(), ParseError::UnusedVariable("x1".to_string()));
+
+        let parsed = Circuit::<Fr>::from_expression("x0 + x1 * x2", &names).unwrap();
+        assert_eq!(parsed.output_value(&[Fr::from(2), Fr::from(3), Fr::from(4)]), Ok(Fr::from(14)));
+    }
+
     #[test]
     fn test_addi() {
         // Define a simple circuit with one layer and one Add gate
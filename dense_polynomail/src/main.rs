@@ -8,7 +8,8 @@ impl DensePolynomial {
     }
 
     fn degree(&self) -> usize {
-        self.coefficients.len() - 1
+        // an empty coefficient vector is the zero polynomial
+        self.coefficients.len().saturating_sub(1)
     }
 
     fn evaluate(&self, x: f64) -> f64 {
@@ -33,14 +33,6 @@ impl Transcript {
         F::from_be_bytes_mod_order(&challenge)
     }
 
-    // Sample a field element (alternative implementation)
-    pub fn sample_element<F: PrimeField>(&mut self) -> F {
-        let mut hash = [0; 32];
-        hash.copy_from_slice(&self.hasher.finalize_reset());
-        Update::update(&mut self.hasher, &hash);
-        F::from_be_bytes_mod_order(&hash)
-    }
-
     // Sample multiple field elements from the transcript
     pub fn sample_n_field_elements<F: PrimeField>(&mut self, n: usize) -> Vec<F> {
         (0..n).map(|_| self.sample_field_element::<F>()).collect()
@@ -1,12 +1,26 @@
 use ark_ff::PrimeField;
+use multilinear::multilinear::MultivariatePoly;
+use multilinear::sparse::SparseMultilinear;
+use std::collections::HashSet;
+use std::ops::Range;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum OP{
   ADD,
   MUL
 }
 
-#[derive(Debug)]
+// Why a `circuit` crate circuit has no GKR counterpart; `layer` counts from the inputs up and `position` is the
+// gate's place within that layer
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ConversionError {
+  // GKR's add/mul predicates can't express a subtraction gate
+  SubtractionGate { layer: usize, position: usize },
+  // the gate reads a wire outside the layer directly below it
+  SkippedLayer { layer: usize, position: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct Gate {
   pub(crate) left_input: usize,
   pub(crate) right_input: usize,
@@ -22,6 +36,21 @@ impl Gate {
   }
 }
 
+// how the variables of a layer's wiring polynomials split into output, left and right index groups,
+// in the MSB-first order used by `convert_to_binary_and_to_decimal`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LayerLayout {
+  pub(crate) output_bits: usize,
+  pub(crate) bits_per_index: usize,
+  pub(crate) total_vars: usize,
+  pub(crate) output_range: Range<usize>,
+  pub(crate) left_range: Range<usize>,
+  pub(crate) right_range: Range<usize>,
+}
+
+// Layers run from the output to the inputs: `gates[0]` computes the output and `layers[0]` holds its
+// values, while the last `layers` entry is the inputs. The `circuit` crate stores the other direction;
+// its `output_to_input_layers` gives the order used here.
 #[derive(Debug)]
 pub(crate) struct Circuit<F: PrimeField> {
   pub(crate) layers: Vec<Vec<F>>,
@@ -33,6 +62,105 @@ impl <F: PrimeField> Circuit<F> {
     Circuit { layers: vec![], gates }
   }
 
+  // number of values feeding the gates of `layer_index`; the last gate layer reads the inputs,
+  // so its size is inferred from the highest wire it references
+  pub(crate) fn layer_input_len(&self, layer_index: usize) -> usize {
+    if layer_index + 1 < self.gates.len() {
+      self.gates[layer_index + 1].len()
+    } else {
+      self.gates[layer_index].iter().map(|x| x.left_input.max(x.right_input)).max().unwrap() + 1
+    }
+  }
+
+  // number of variables of the add/mul wiring polynomials of `layer_index`: gate bits + left bits + right bits
+  pub fn layer_num_vars(&self, layer_index: usize) -> usize {
+    self.gkr_layout(layer_index).total_vars
+  }
+
+  // `bits_per_index` is the width of the left and right input indices; the output index is
+  // sized by the layer's own gate count
+  pub(crate) fn gkr_layout(&self, layer_index: usize) -> LayerLayout {
+    let output_bits = num_of_layer_variables(self.gates[layer_index].len());
+    let bits_per_index = num_of_layer_variables(self.layer_input_len(layer_index));
+    let left_start = output_bits;
+    let right_start = left_start + bits_per_index;
+    let total_vars = right_start + bits_per_index;
+
+    LayerLayout {
+      output_bits,
+      bits_per_index,
+      total_vars,
+      output_range: 0..left_start,
+      left_range: left_start..right_start,
+      right_range: right_start..total_vars,
+    }
+  }
+
+  // wiring predicate of `layer_index` for one gate type: one at the packed (output, left, right) index of every
+  // gate of that type, zero elsewhere. Panics if two different gates write the same output.
+  pub(crate) fn sparse_wiring_poly(&self, layer_index: usize, gate_type: OP) -> SparseMultilinear<F> {
+    let layout = self.gkr_layout(layer_index);
+    let mut entries = vec![];
+
+    let layer = &self.gates[layer_index];
+    for (j, gate) in layer.iter().enumerate() {
+      if let Some(other) = layer[..j].iter().find(|other| other.output == gate.output) {
+        if other != gate {
+          panic!("Conflicting gates {:?} and {:?} in layer {} write output {}", other, gate, layer_index, gate.output);
+        }
+        continue;
+      }
+      if gate.op == gate_type {
+        entries.push((convert_to_binary_and_to_decimal(gate.output, gate.left_input, gate.right_input, layout.bits_per_index), F::one()));
+      }
+    }
+
+    SparseMultilinear::new(entries, layout.total_vars)
+  }
+
+  pub(crate) fn wiring_poly(&self, layer_index: usize, gate_type: OP) -> MultivariatePoly<F> {
+    self.sparse_wiring_poly(layer_index, gate_type).to_dense()
+  }
+
+  // removes gates that exactly repeat an earlier gate of the same layer
+  pub(crate) fn dedup_gates(&mut self) {
+    for layer in self.gates.iter_mut() {
+      let mut seen = HashSet::new();
+      layer.retain(|gate| seen.insert(gate.clone()));
+    }
+  }
+
+  // Canonical encoding of the gate structure, independent of the order gates are listed in a layer:
+  // layer count, then per layer the gate count and each (output, left, right, op) in sorted order, all as
+  // big-endian u64 with op 0 for ADD and 1 for MUL
+  pub(crate) fn structure_bytes(&self) -> Vec<u8> {
+    let mut bytes = (self.gates.len() as u64).to_be_bytes().to_vec();
+    for layer in &self.gates {
+      let mut gates: Vec<[u64; 4]> = layer
+        .iter()
+        .map(|gate| {
+          let op = match gate.op {
+            OP::ADD => 0,
+            OP::MUL => 1,
+          };
+          [gate.output as u64, gate.left_input as u64, gate.right_input as u64, op]
+        })
+        .collect();
+      gates.sort();
+      bytes.extend((gates.len() as u64).to_be_bytes());
+      gates.iter().flatten().for_each(|x| bytes.extend(x.to_be_bytes()));
+    }
+    bytes
+  }
+
+  pub(crate) fn addi(&self, layer_index: usize) -> MultivariatePoly<F> {
+    self.wiring_poly(layer_index, OP::ADD)
+  }
+
+  pub(crate) fn muli(&self, layer_index: usize) -> MultivariatePoly<F> {
+    self.wiring_poly(layer_index, OP::MUL)
+  }
+
   pub(crate) fn evaluate(&mut self, inputs: &Vec<F>) -> Vec<Vec<F>> {
     let layers_len = self.gates.len() + 1;
     let mut layer_values = vec![vec![]; layers_len];
@@ -65,6 +193,91 @@ impl <F: PrimeField> Circuit<F> {
   }
 }
 
+// keeps the input indices, relative to the layer below, and drops the output index, which the `circuit`
+// crate takes from the gate's position in its layer
+impl From<Gate> for circuit::circuit::Gate {
+  fn from(gate: Gate) -> Self {
+    match gate.op {
+      OP::ADD => circuit::circuit::Gate::Add(gate.left_input, gate.right_input),
+      OP::MUL => circuit::circuit::Gate::Mul(gate.left_input, gate.right_input),
+    }
+  }
+}
+
+// The `circuit` crate lists layers from the inputs up and numbers wires globally (inputs, then every
+// layer's outputs in order), so each layer is reversed into place, sorted by output and offset to where
+// the layer below starts. A circuit without gate layers converts to an empty one.
+impl<F: PrimeField> From<&Circuit<F>> for circuit::circuit::Circuit<F> {
+  fn from(gkr_circuit: &Circuit<F>) -> Self {
+    let mut converted = circuit::circuit::Circuit::new();
+    if gkr_circuit.gates.is_empty() {
+      return converted;
+    }
+    let mut offset = 0;
+    let mut below_len = gkr_circuit.layer_input_len(gkr_circuit.gates.len() - 1);
+
+    for layer in gkr_circuit.gates.iter().rev() {
+      let mut layer = layer.clone();
+      layer.sort_by_key(|gate| gate.output);
+      let gates = layer.into_iter().map(|gate| {
+        let shifted = Gate::new(gate.left_input + offset, gate.right_input + offset, gate.op, gate.output);
+        circuit::circuit::Gate::from(shifted)
+      }).collect::<Vec<_>>();
+
+      offset += below_len;
+      below_len = gates.len();
+      converted.add_layer(gates);
+    }
+    converted
+  }
+}
+
+// The other direction: each positional gate gets its position as the explicit output and its global wire
+// indices made relative to the layer below. Fails on a subtraction gate or on a gate reading anything but
+// the layer directly below it.
+impl<F: PrimeField> TryFrom<&circuit::circuit::Circuit<F>> for Circuit<F> {
+  type Error = ConversionError;
+
+  fn try_from(circuit: &circuit::circuit::Circuit<F>) -> Result<Self, Self::Error> {
+    let num_inputs = circuit.inferred_num_inputs();
+    let layers = circuit.output_to_input_layers();
+    // walking down from the output, each layer's wires end where the one above starts
+    let mut layer_start = num_inputs + layers.iter().map(|layer| layer.len()).sum::<usize>();
+    let mut gates = vec![];
+
+    for (depth, layer) in layers.iter().enumerate() {
+      let layer_index = layers.len() - 1 - depth;
+      let below_len = layers.get(depth + 1).map_or(num_inputs, |below| below.len());
+      layer_start -= layer.len();
+      let below = layer_start - below_len..layer_start;
+      let converted = layer.iter().enumerate().map(|(position, gate)| {
+        let (left, right, op) = match *gate {
+          circuit::circuit::Gate::Add(a, b) => (a, b, OP::ADD),
+          circuit::circuit::Gate::Mul(a, b) => (a, b, OP::MUL),
+          circuit::circuit::Gate::Sub(..) => return Err(ConversionError::SubtractionGate { layer: layer_index, position }),
+        };
+        if !below.contains(&left) || !below.contains(&right) {
+          return Err(ConversionError::SkippedLayer { layer: layer_index, position });
+        }
+        Ok(Gate::new(left - below.start, right - below.start, op, position))
+      }).collect::<Result<Vec<_>, _>>()?;
+      gates.push(converted);
+    }
+    Ok(Circuit::new(gates))
+  }
+}
+
+// number of boolean variables needed to index `layer_len` values, at least 1 so a single value still has a variable
+pub fn num_of_layer_variables(layer_len: usize) -> usize {
+  let bits = layer_len.next_power_of_two().trailing_zeros() as usize;
+  if bits == 0 { 1 } else { bits }
+}
+
+// packs a gate's (output, left, right) wires into the index of its entry in the wiring polynomial,
+// laid out MSB first as output bits || left bits || right bits
+pub fn convert_to_binary_and_to_decimal(output: usize, left_input: usize, right_input: usize, input_bits: usize) -> usize {
+  (output << (input_bits * 2)) | (left_input << input_bits) | right_input
+}
 
 #[cfg(test)]
 
@@ -97,6 +310,102 @@ mod test {
     );
   }
 
+  #[test]
+  fn test_conversion_to_circuit_crate_evaluates_the_same() {
+    let gates = vec![
+      vec![
+        Gate::new(0, 1, OP::MUL, 0),
+      ],
+      vec![
+        Gate::new(2, 3, OP::MUL, 1),
+        Gate::new(0, 1, OP::ADD, 0),
+      ],
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+        Gate::new(4, 5, OP::MUL, 2),
+        Gate::new(6, 7, OP::ADD, 3),
+      ]
+    ];
+    let mut gkr_circuit: Circuit<Fq> = Circuit::new(gates);
+    let converted = circuit::circuit::Circuit::from(&gkr_circuit);
+
+    let inputs: Vec<Fq> = (1..=8u64).map(Fq::from).collect();
+    let mut expected = gkr_circuit.evaluate(&inputs);
+    expected.reverse();
+    assert_eq!(converted.try_evaluate(inputs).unwrap(), expected);
+
+    // read back in GKR order, every layer lines up with the gkr gates of the same index
+    let layers = converted.output_to_input_layers();
+    assert_eq!(layers.len(), gkr_circuit.gates.len());
+    for (layer, gkr_layer) in layers.iter().zip(gkr_circuit.gates.iter()) {
+      let mut gkr_layer = gkr_layer.clone();
+      gkr_layer.sort_by_key(|gate| gate.output);
+      for (gate, gkr_gate) in layer.iter().zip(gkr_layer) {
+        let is_add = matches!(gate, circuit::circuit::Gate::Add(..));
+        assert_eq!(is_add, gkr_gate.op == OP::ADD);
+      }
+    }
+  }
+
+  #[test]
+  fn test_conversion_to_circuit_crate_of_an_empty_circuit() {
+    let converted = circuit::circuit::Circuit::from(&Circuit::<Fq>::new(vec![]));
+    assert!(converted.layers().is_empty());
+  }
+
+  #[test]
+  fn test_conversion_from_circuit_crate_has_the_same_wiring() {
+    let gates = vec![
+      vec![
+        Gate::new(0, 1, OP::MUL, 0),
+        Gate::new(2, 3, OP::ADD, 1),
+      ],
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+        Gate::new(4, 5, OP::MUL, 2),
+        Gate::new(6, 7, OP::ADD, 3),
+      ]
+    ];
+    let mut hand_built: Circuit<Fq> = Circuit::new(gates);
+
+    let mut positional = circuit::circuit::Circuit::new();
+    positional.add_layer(vec![
+      circuit::circuit::Gate::Add(0, 1),
+      circuit::circuit::Gate::Mul(2, 3),
+      circuit::circuit::Gate::Mul(4, 5),
+      circuit::circuit::Gate::Add(6, 7),
+    ]);
+    positional.add_layer(vec![circuit::circuit::Gate::Mul(8, 9), circuit::circuit::Gate::Add(10, 11)]);
+    let mut converted = Circuit::<Fq>::try_from(&positional).unwrap();
+
+    assert_eq!(converted.gates, hand_built.gates);
+    for layer_index in 0..2 {
+      assert_eq!(converted.addi(layer_index), hand_built.addi(layer_index));
+      assert_eq!(converted.muli(layer_index), hand_built.muli(layer_index));
+    }
+
+    let inputs: Vec<Fq> = (1..=8u64).map(Fq::from).collect();
+    assert_eq!(converted.evaluate(&inputs), hand_built.evaluate(&inputs));
+  }
+
+  #[test]
+  fn test_conversion_from_circuit_crate_rejects_skipped_layers() {
+    let mut positional = circuit::circuit::Circuit::<Fq>::new();
+    positional.add_layer(vec![circuit::circuit::Gate::Add(0, 1), circuit::circuit::Gate::Mul(2, 3)]);
+    // reads input 0 from two layers down
+    positional.add_layer(vec![circuit::circuit::Gate::Add(0, 4)]);
+    assert_eq!(Circuit::<Fq>::try_from(&positional).unwrap_err(), ConversionError::SkippedLayer { layer: 1, position: 0 });
+  }
+
+  #[test]
+  fn test_conversion_from_circuit_crate_rejects_subtraction() {
+    let mut positional = circuit::circuit::Circuit::<Fq>::new();
+    positional.add_layer(vec![circuit::circuit::Gate::Add(0, 1), circuit::circuit::Gate::Sub(2, 3)]);
+    assert_eq!(Circuit::<Fq>::try_from(&positional).unwrap_err(), ConversionError::SubtractionGate { layer: 0, position: 1 });
+  }
+
   #[test]
   fn test_evaluate2() {
     let gates = vec![
@@ -124,4 +433,176 @@ mod test {
       output
     );
   }
+
+  #[test]
+  fn test_num_of_layer_variables() {
+    assert_eq!(num_of_layer_variables(1), 1);
+    assert_eq!(num_of_layer_variables(2), 1);
+    assert_eq!(num_of_layer_variables(3), 2);
+    assert_eq!(num_of_layer_variables(4), 2);
+    assert_eq!(num_of_layer_variables(5), 3);
+    assert_eq!(num_of_layer_variables(8), 3);
+  }
+
+  #[test]
+  fn test_layer_num_vars() {
+    let gates = vec![
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+      ],
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+        Gate::new(4, 5, OP::MUL, 2),
+        Gate::new(6, 7, OP::MUL, 3),
+      ]
+    ];
+    let circuit: Circuit<Fq> = Circuit::new(gates);
+
+    // 1 gate bit + 2 * 2 input bits
+    assert_eq!(circuit.layer_num_vars(0), 5);
+    // 2 gate bits + 2 * 3 input bits
+    assert_eq!(circuit.layer_num_vars(1), 8);
+  }
+
+  #[test]
+  fn test_gkr_layout() {
+    let circuit: Circuit<Fq> = Circuit::new(vec![
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+        Gate::new(1, 2, OP::ADD, 2),
+        Gate::new(3, 0, OP::MUL, 3),
+      ]
+    ]);
+
+    let layout = circuit.gkr_layout(0);
+    assert_eq!(layout.bits_per_index, 2);
+    assert_eq!(layout.output_bits, 2);
+    assert_eq!(layout.total_vars, 6);
+    assert_eq!(layout.output_range, 0..2);
+    assert_eq!(layout.left_range, 2..4);
+    assert_eq!(layout.right_range, 4..6);
+  }
+
+  #[test]
+  fn test_wiring_poly() {
+    let gates = vec![
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+      ]
+    ];
+    let circuit: Circuit<Fq> = Circuit::new(gates);
+
+    // 1 gate bit || 2 left bits || 2 right bits
+    let add = circuit.addi(0);
+    let mul = circuit.muli(0);
+    assert_eq!(add.num_vars, 5);
+    assert_eq!(add.coeffs.iter().filter(|x| **x == Fq::from(1)).count(), 1);
+    assert_eq!(add.coeffs[0b0_00_01], Fq::from(1));
+    assert_eq!(mul.coeffs.iter().filter(|x| **x == Fq::from(1)).count(), 1);
+    assert_eq!(mul.coeffs[0b1_10_11], Fq::from(1));
+  }
+
+  #[test]
+  fn test_sparse_wiring_poly_matches_dense() {
+    let circuit: Circuit<Fq> = Circuit::new(vec![
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+        Gate::new(1, 2, OP::ADD, 2),
+      ],
+      vec![
+        Gate::new(0, 1, OP::MUL, 0),
+        Gate::new(2, 3, OP::ADD, 1),
+        Gate::new(4, 5, OP::MUL, 2),
+      ]
+    ]);
+
+    for layer_index in 0..2 {
+      for gate_type in [OP::ADD, OP::MUL] {
+        let sparse = circuit.sparse_wiring_poly(layer_index, gate_type);
+        let dense = circuit.wiring_poly(layer_index, gate_type);
+        for seed in 1..4u64 {
+          let point: Vec<Fq> = (0..dense.num_vars as u64).map(|i| Fq::from(seed * 7919 + i * i * 104729)).collect();
+          assert_eq!(sparse.evaluate(&point), dense.evaluate(&point));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_dedup_gates() {
+    let mut duplicated: Circuit<Fq> = Circuit::new(vec![
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+      ]
+    ]);
+    let expected: Circuit<Fq> = Circuit::new(vec![
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(2, 3, OP::MUL, 1),
+      ]
+    ]);
+
+    duplicated.dedup_gates();
+    assert_eq!(duplicated.gates, expected.gates);
+    assert_eq!(duplicated.addi(0), expected.addi(0));
+    assert_eq!(duplicated.muli(0), expected.muli(0));
+  }
+
+  #[test]
+  #[should_panic(expected = "Conflicting gates")]
+  fn test_wiring_poly_rejects_conflicting_outputs() {
+    let circuit: Circuit<Fq> = Circuit::new(vec![
+      vec![
+        Gate::new(0, 1, OP::ADD, 0),
+        Gate::new(0, 1, OP::MUL, 0),
+      ]
+    ]);
+    circuit.addi(0);
+  }
+
+  #[test]
+  fn test_convert_to_binary_and_to_decimal_against_old_wiring_layout() {
+    // the layout get_add_and_muls used to build by hand: the gate's position in the layer as the output
+    // bits, and an input layer sized from its largest index rather than its length
+    let old_bits = |n: usize| if n == 0 { 1 } else { (n as f64).log2().ceil() as usize };
+    let old_index = |position: usize, left: usize, right: usize, bits: usize| (position << (bits * 2)) + (left << bits) + right;
+
+    // gates listed in output order over 8 inputs (largest index 7): both layouts agree
+    let gates = [Gate::new(0, 1, OP::ADD, 0), Gate::new(2, 3, OP::MUL, 1), Gate::new(4, 5, OP::MUL, 2), Gate::new(6, 7, OP::ADD, 3)];
+    let bits = num_of_layer_variables(8);
+    assert_eq!(bits, old_bits(7));
+    for (position, gate) in gates.iter().enumerate() {
+      assert_eq!(
+        convert_to_binary_and_to_decimal(gate.output, gate.left_input, gate.right_input, bits),
+        old_index(position, gate.left_input, gate.right_input, bits)
+      );
+    }
+
+    // listed out of order, the old layout wired each gate to whichever output its position named
+    let swapped = [Gate::new(2, 3, OP::MUL, 1), Gate::new(0, 1, OP::ADD, 0)];
+    assert_eq!(convert_to_binary_and_to_decimal(swapped[0].output, 2, 3, bits), old_index(1, 2, 3, bits));
+    assert_ne!(convert_to_binary_and_to_decimal(swapped[0].output, 2, 3, bits), old_index(0, 2, 3, bits));
+
+    // 5 inputs, so the largest index is the power of two 4: the old sizing gave 2 bits, too few for index 4,
+    // and a gate reading it collided with the next output's entry
+    assert_eq!(old_bits(4), 2);
+    assert_eq!(num_of_layer_variables(5), 3);
+    assert_eq!(old_index(0, 4, 0, 2), old_index(1, 0, 0, 2));
+    assert_ne!(convert_to_binary_and_to_decimal(0, 4, 0, 3), convert_to_binary_and_to_decimal(1, 0, 0, 3));
+  }
+
+  #[test]
+  fn test_structure_bytes_ignores_gate_order() {
+    let a: Circuit<Fq> = Circuit::new(vec![vec![Gate::new(0, 1, OP::ADD, 0), Gate::new(2, 3, OP::MUL, 1)]]);
+    let b: Circuit<Fq> = Circuit::new(vec![vec![Gate::new(2, 3, OP::MUL, 1), Gate::new(0, 1, OP::ADD, 0)]]);
+    let c: Circuit<Fq> = Circuit::new(vec![vec![Gate::new(0, 1, OP::MUL, 0), Gate::new(2, 3, OP::MUL, 1)]]);
+    assert_eq!(a.structure_bytes(), b.structure_bytes());
+    assert_ne!(a.structure_bytes(), c.structure_bytes());
+  }
 }
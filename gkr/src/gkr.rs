@@ -1,14 +1,14 @@
-use std::cmp::max;
 use std::iter::repeat_n;
 use std::ops::Mul;
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::PrimeField;
 use multilinear::multilinear::MultivariatePoly;
-use multilinear::composite::{Composite, OP as COMPOSITE_OP};
-use crate::circut::{ Circuit, OP as CIRCUIT_OP, Gate};
+use multilinear::composite::CompositeTree;
+use crate::circut::{ Circuit, num_of_layer_variables};
+use crate::protocol::TranscriptProtocol;
 use prime_polynomail::DensePolynomial;
 use transcript::transcript::{Transcript, HashTrait, TranscriptTrait};
 use std::marker::PhantomData;
-use sumcheck::sumcheck::{add_data_to_transcript, generate_partial_proof, verify_partial_proof, verify_partial_proof_2};
+use sumcheck::sumcheck::{generate_partial_proof, verify_partial_proof, verify_partial_proof_2};
 use transcript::transcript::KeccakWrapper;
 
 
@@ -23,7 +23,7 @@ struct GKR_PROOF<F: PrimeField> {
   output: Vec<F>
 }
 
-fn generate_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(circuit: &mut Circuit<F>, inputs: &Vec<F>, transcript: &mut T) -> GKR_PROOF<F> {
+fn generate_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(circuit: &mut Circuit<F>, inputs: &Vec<F>, transcript: &mut TranscriptProtocol<F, T>) -> GKR_PROOF<F> {
   circuit.evaluate(inputs);
   let mut gkr_proof = GKR_PROOF {
       claimed_sums: vec![],
@@ -35,69 +35,45 @@ fn generate_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(circuit: &
   let mut add_and_muls = vec![];
   get_add_and_muls(&circuit, &mut add_and_muls);
 
-  let mut _w = circuit.layers[0].clone();
-
-  if _w.len() == 1 {
-      _w = vec![_w[0], F::zero()];
-  }
-  let num_variables = (_w.len() as f64).log2().ceil() as usize;
-  dbg!(num_variables);
+  transcript.absorb_circuit(&circuit.structure_bytes());
+  let _w = circuit.layers[0].clone();
   dbg!(&_w);
   println!("=============?????????>>>>>>>>>working");
-  let w_i = MultivariatePoly::new(_w, num_variables);
-  let challenges_length = next_pow_of_2(w_i.coeffs.len());
-  let mut challenges = vec![F::zero(); challenges_length];
-  add_data_to_transcript::<F, H, T>(&w_i.coeffs, transcript);
-  let squeezed = transcript.squeeze();
-  let squeezed_bytes = squeezed.into_bigint().to_bytes_be();
-  challenges = challenges.iter().map(|_| F::from_be_bytes_mod_order(&squeezed_bytes)).collect();
+  // a single output still gets one variable, padded with a zero evaluation
+  let w_i = MultivariatePoly::from_coeffs(_w).with_num_vars(num_of_layer_variables(circuit.layers[0].len())).unwrap();
+  let challenges_length = num_of_layer_variables(w_i.coeffs.len());
+  transcript.absorb_output(&w_i.coeffs);
+  let mut challenges = transcript.squeeze_initial_challenges(challenges_length);
 
   for i in 0..circuit.gates.len() {
       let (mut add_poly, mut mul_poly) = add_and_muls[i].clone();
 
-      let num_variables = (circuit.layers[i + 1].len() as f64).log2().ceil() as usize;
-      let w_i_plus_1 = MultivariatePoly::new(circuit.layers[i + 1].clone(), num_variables);
-      let blows = next_pow_of_2(w_i_plus_1.coeffs.len()) as u32;
-      // blow ups
-      let w_b = w_i_plus_1.blow_up_right(blows); // blow up for c
-      let w_c = w_i_plus_1.blow_up_left(blows); // blow up for b
-      let num_variables = (w_b.coeffs.len() as f64).log2().ceil() as usize;
-      let w_plus = MultivariatePoly::new(w_b.coeffs.clone(), num_variables) + MultivariatePoly::new(w_c.coeffs.clone(), num_variables);
-      let w_mul = MultivariatePoly::new(w_b.coeffs.clone(), num_variables) * MultivariatePoly::new(w_c.coeffs.clone(), num_variables);
-
-      if i != 0 {
-          let alpha = F::from_be_bytes_mod_order(&transcript.squeeze().into_bigint().to_bytes_be());
-          let beta = F::from_be_bytes_mod_order(&transcript.squeeze().into_bigint().to_bytes_be());
-          add_poly = apply_alpha_beta(alpha, beta, &challenges, &add_poly);
-          mul_poly = apply_alpha_beta(alpha, beta, &challenges, &mul_poly);
-      } else {
-          add_poly = add_poly.solve(&challenges.iter().map(|x| Some(*x)).collect());
-          mul_poly = mul_poly.solve(&challenges.iter().map(|x| Some(*x)).collect());
-      }
-
-      let hypercubes = vec![
-          add_poly,
-          MultivariatePoly::new(w_plus.coeffs.clone(), (w_plus.coeffs.len() as f64).log2().ceil() as usize),
-          mul_poly,
-          MultivariatePoly::new(w_mul.coeffs.clone(), (w_mul.coeffs.len() as f64).log2().ceil() as usize),
-      ]
-      .iter()
-      .map(|x| x.coeffs.clone())
-      .collect();
+      let w_i_plus_1 = MultivariatePoly::from_coeffs(circuit.layers[i + 1].clone());
+      let layout = circuit.gkr_layout(i);
+      let blows = layout.bits_per_index as u32;
 
-      let f_poly = Composite::new(
-          &hypercubes,
-          vec![COMPOSITE_OP::MUL, COMPOSITE_OP::ADD, COMPOSITE_OP::MUL],
-      );
       let mut round_polys = vec![];
-      challenges = vec![];
-      // returns challenges and initial claimed sum
-      let sum = generate_partial_proof::<F, H, T>(&f_poly, transcript, &mut round_polys, &mut challenges);
-
-      let w_b_eval = w_i_plus_1.solve(&challenges.iter().take(blows as usize).map(|x| Some(*x)).collect()).coeffs[0];
-      let w_c_eval = w_i_plus_1.solve(&challenges.iter().skip(blows as usize).map(|x| Some(*x)).collect()).coeffs[0];
-
-      add_data_to_transcript::<F, H, T>(&vec![w_b_eval, w_c_eval], transcript);
+      let (mut sum, mut w_b_eval, mut w_c_eval) = (F::zero(), F::zero(), F::zero());
+      transcript.with_layer(i, |transcript| {
+        if i != 0 {
+            let (alpha, beta) = transcript.squeeze_alpha_beta();
+            add_poly = apply_alpha_beta(alpha, beta, &challenges, &add_poly);
+            mul_poly = apply_alpha_beta(alpha, beta, &challenges, &mul_poly);
+        } else {
+            add_poly = add_poly.solve(&challenges.iter().map(|x| Some(*x)).collect());
+            mul_poly = mul_poly.solve(&challenges.iter().map(|x| Some(*x)).collect());
+        }
+
+        let f_poly = layer_composite(add_poly, mul_poly, &w_i_plus_1, blows);
+        challenges = vec![];
+        // returns challenges and initial claimed sum
+        sum = generate_partial_proof::<F, H, TranscriptProtocol<F, T>>(&f_poly, transcript, &mut round_polys, &mut challenges);
+
+        w_b_eval = w_i_plus_1.fold_all(&challenges[..blows as usize]);
+        w_c_eval = w_i_plus_1.fold_all(&challenges[blows as usize..]);
+
+        transcript.absorb_evaluations(w_b_eval, w_c_eval);
+      });
 
       gkr_proof.claimed_sums.push(sum);
       gkr_proof.round_polys.push(round_polys.iter().map(|poly| poly.coefficients.clone()).collect());
@@ -111,7 +87,7 @@ fn generate_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(circuit: &
 
 
 
-fn verify_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>> (circuit: &mut Circuit<F>, inputs: &Vec<F>, transcript: &mut T, gkr_proof: GKR_PROOF<F>) -> bool {  let mut add_and_muls = vec![];
+fn verify_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>> (circuit: &mut Circuit<F>, inputs: &Vec<F>, transcript: &mut TranscriptProtocol<F, T>, gkr_proof: GKR_PROOF<F>) -> bool {  let mut add_and_muls = vec![];
   get_add_and_muls(&circuit, &mut add_and_muls);
 
   let evaluations = gkr_proof.evaluations;
@@ -120,50 +96,50 @@ fn verify_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>> (circuit: &m
       .map(|poly_vec| poly_vec.iter().map(|coeffs| DensePolynomial::new(coeffs.clone())).collect())
       .collect();
 
-  let mut _w = gkr_proof.output;
-  if _w.len() == 1 { _w.push(F::zero()) }
-  let num_variables = (_w.len() as f64).log2().ceil() as usize;
-  dbg!(num_variables);
+  transcript.absorb_circuit(&circuit.structure_bytes());
+  let _w = gkr_proof.output;
   dbg!(&_w);
-  let w_i = MultivariatePoly::new(_w, num_variables);
-
-  let challenges_length = next_pow_of_2(w_i.coeffs.len());  
-  let mut challenges = vec![F::zero(); challenges_length];
+  let output_vars = num_of_layer_variables(_w.len());
+  let w_i = MultivariatePoly::from_coeffs(_w).with_num_vars(output_vars).unwrap();
 
-  add_data_to_transcript::<F, H, T>(&w_i.coeffs, transcript);
-  challenges = challenges.iter().map(|_| F::from_be_bytes_mod_order(&transcript.squeeze().into_bigint().to_bytes_be())).collect();  
+  let challenges_length = num_of_layer_variables(w_i.coeffs.len());  
+  transcript.absorb_output(&w_i.coeffs);
+  let mut challenges = transcript.squeeze_initial_challenges(challenges_length);
 
   let last_index = circuit.gates.len()-1;
   for i in 0..circuit.gates.len(){
-    // follows order of transcript call to ensure it gets the same challenges as prover
-    // so alpha and beta are fetched before verify_partial_proof is called even though they aren't used
     let (mut alpha, mut beta)  = (F::zero(), F::zero());
-    if i != 0 {
-      alpha = F::from_be_bytes_mod_order(&transcript.squeeze().into_bigint().to_bytes_be());
-      beta = F::from_be_bytes_mod_order(&transcript.squeeze().into_bigint().to_bytes_be()); 
-    }
-    let polys_2: Vec<Vec<F>> = round_polys[i].iter().map(|p| p.coefficients.clone()).collect();
-    let (sum, new_challenges, success) = verify_partial_proof_2::<F, H, T>(claimed_sums[i], &polys_2, transcript);
-    if !success { return false; }
-    let (mut add_poly, mut mul_poly) = add_and_muls[i].clone();
+    let (mut sum, mut new_challenges, mut success) = (F::zero(), vec![], false);
+    let (mut w_plus, mut w_mul) = (F::zero(), F::zero());
+    transcript.with_layer(i, |transcript| {
+      // follows order of transcript call to ensure it gets the same challenges as prover
+      // so alpha and beta are fetched before verify_partial_proof is called even though they aren't used
+      if i != 0 {
+        (alpha, beta) = transcript.squeeze_alpha_beta();
+      }
+      let polys_2: Vec<Vec<F>> = round_polys[i].iter().map(|p| p.coefficients.clone()).collect();
+      (sum, new_challenges, success) = verify_partial_proof_2::<F, H, TranscriptProtocol<F, T>>(claimed_sums[i], &polys_2, transcript);
+      if !success { return; }
 
-    let (w_b_eval, w_c_eval, w_plus, w_mul);
-    if i < last_index {
-      (w_b_eval, w_c_eval) = evaluations[i];
-      (w_plus , w_mul) = (w_b_eval + w_c_eval, w_b_eval * w_c_eval);
-    } else {
-      // last layer 
-      let num_variables = (inputs.len() as f64).log2().ceil() as usize;
-      let w_inputs = MultivariatePoly::new(inputs.clone(), num_variables);
-      let challenges_len = new_challenges.len() / 2;
-      let b_challenges = new_challenges.iter().take(challenges_len).map(|x| Some(*x)).collect();
-      let c_challenges = new_challenges.iter().skip(challenges_len).take(challenges_len).map(|x| Some(*x)).collect();
-      w_b_eval = w_inputs.solve(&b_challenges).coeffs[0];
-      w_c_eval = w_inputs.solve(&c_challenges).coeffs[0];
+      let (w_b_eval, w_c_eval);
+      if i < last_index {
+        (w_b_eval, w_c_eval) = evaluations[i];
+      } else {
+        // last layer 
+        let w_inputs = MultivariatePoly::from_coeffs(inputs.clone());
+        // sumcheck ran over the left and right groups only, once the output variables were bound
+        let layout = circuit.gkr_layout(i);
+        let b_challenges = &new_challenges[layout.left_range.start - layout.output_bits..layout.left_range.end - layout.output_bits];
+        let c_challenges = &new_challenges[layout.right_range.start - layout.output_bits..layout.right_range.end - layout.output_bits];
+        w_b_eval = w_inputs.fold_all(b_challenges);
+        w_c_eval = w_inputs.fold_all(c_challenges);
+      }
       (w_plus, w_mul) = (w_b_eval + w_c_eval, w_b_eval * w_c_eval);
-    }
 
-    add_data_to_transcript::<F, H, T>(&vec![w_b_eval, w_c_eval], transcript);
+      transcript.absorb_evaluations(w_b_eval, w_c_eval);
+    });
+    if !success { return false; }
+    let (mut add_poly, mut mul_poly) = add_and_muls[i].clone();
     
     
     if i != 0 {
@@ -178,7 +154,7 @@ fn verify_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>> (circuit: &m
       add_poly = add_poly.scalar_mul( w_plus);
 
     let f_poly = mul_poly + add_poly;
-    let evaluated_sum = f_poly.solve(&new_challenges.iter().map(|x| Some(*x)).collect()).coeffs[0];
+    let evaluated_sum = f_poly.fold_all(&new_challenges);
     if sum != evaluated_sum {
       return false;
     }
@@ -191,48 +167,16 @@ fn verify_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>> (circuit: &m
 
 fn get_add_and_muls<F: PrimeField> (circuit: &Circuit<F>, add_and_muls: &mut Vec<(MultivariatePoly<F>, MultivariatePoly<F>)> ) {
   for i in 0..circuit.gates.len() {
-    let gates_length = circuit.gates[i].len();
-    let layer_length;
-    if circuit.gates.len() <= i + 1 {
-      layer_length = circuit.gates[i].iter().map(|x| max(x.left_input, x.right_input)).max().unwrap();
-    } else {
-      layer_length = circuit.gates[i+1].len();
-    }
-    let max_layer_bits = next_pow_of_2(layer_length);
-    let max_gates_bits = next_pow_of_2(gates_length);
-
-    let points_len = 1 << max_gates_bits + (max_layer_bits*2);
-    let mut add_poly = vec![F::zero(); points_len];
-    let mut mul_poly = vec![F::zero(); points_len];
-
-    for (j, gate) in circuit.gates[i].iter().enumerate() {
-      let index = (j << max_layer_bits * 2) // gate bits
-          + (gate.left_input << max_layer_bits) // left_input bits
-          + gate.right_input; // right_input bits
-      match gate.op {
-        CIRCUIT_OP::ADD => add_poly[index] = F::one(),
-        CIRCUIT_OP::MUL => mul_poly[index] = F::one()
-      }
-    }
-
-    let num_variables = (add_poly.len() as f64).log2().ceil() as usize;
-    add_and_muls.push((MultivariatePoly::new(add_poly, num_variables), MultivariatePoly::new(mul_poly, num_variables)));
-
-    // f_polys.push(FPOLY::new(mul_poly, add_poly, layer.clone()))
+    add_and_muls.push((circuit.addi(i), circuit.muli(i)));
   }  
 }
 
-fn next_pow_of_2 (no: usize) -> usize {
-  let toOne = |x: usize| -> usize { if x == 0 {1} else {x}};
-  toOne((no as f64).log2().ceil() as usize)
-}
-
 fn apply_alpha_beta <F: PrimeField> (alpha: F, beta: F, challenges: &Vec<F>, former_op_poly: &MultivariatePoly<F>) -> MultivariatePoly<F> {
   let no_of_challenges = challenges.len()/2;
   let mut polys = vec![];
 
   for  skip in [0, no_of_challenges] {
-    let no_of_variables = (former_op_poly.coeffs.len() as f64).log2() as usize;
+    let no_of_variables = former_op_poly.num_vars;
     let mut _challenges: Vec<Option<F>> = challenges
         .iter()
         .skip(skip)
@@ -244,8 +188,28 @@ fn apply_alpha_beta <F: PrimeField> (alpha: F, beta: F, challenges: &Vec<F>, for
     polys.push(former_op_poly.solve(&_challenges));
   }
 
-  
-  polys[0].scalar_mul(alpha) + polys[1].scalar_mul(beta)
+  // alpha·polys[0] + beta·polys[1], reusing polys[0]'s coefficients for the result
+  let mut scaled_b = polys.pop().unwrap();
+  let mut combined = polys.pop().unwrap();
+  combined.scalar_mul_assign(alpha);
+  scaled_b.scalar_mul_assign(beta);
+  combined += &scaled_b;
+  combined
+}
+
+// The polynomial that sumcheck runs over for one layer: add(b, c)·(w(b) + w(c)) + mul(b, c)·(w(b)·w(c)), with the
+// output variables of `add_poly` and `mul_poly` already bound and `blows` the width of b and c
+fn layer_composite<F: PrimeField>(add_poly: MultivariatePoly<F>, mul_poly: MultivariatePoly<F>, w_i_plus_1: &MultivariatePoly<F>, blows: u32) -> CompositeTree<F> {
+  let w_b = w_i_plus_1.blow_up_right(blows); // blow up for c
+  let w_c = w_i_plus_1.blow_up_left(blows); // blow up for b
+  layer_poly_tree(add_poly, mul_poly, w_b, w_c)
+}
+
+// The layer polynomial add(b, c)·(w(b) + w(c)) + mul(b, c)·(w(b)·w(c)) as a tree, with `w_b` and `w_c` the
+// next layer's evaluations blown up over the c and b variables
+fn layer_poly_tree<F: PrimeField>(add_poly: MultivariatePoly<F>, mul_poly: MultivariatePoly<F>, w_b: MultivariatePoly<F>, w_c: MultivariatePoly<F>) -> CompositeTree<F> {
+  let (w_b, w_c) = (CompositeTree::Leaf(w_b), CompositeTree::Leaf(w_c));
+  CompositeTree::Leaf(add_poly) * (w_b.clone() + w_c.clone()) + CompositeTree::Leaf(mul_poly) * (w_b * w_c)
 }
 
 
@@ -254,6 +218,10 @@ mod test {
   use super::*;
   use ark_bn254::Fq;
   use sha3::{Keccak256, Digest};  
+  use crate::protocol::Phase;
+  use crate::circut::{Gate, OP as CIRCUIT_OP};
+  use rand::{rngs::StdRng, SeedableRng};
+  use multilinear::composite::{Composite, SumcheckPoly, OP as COMPOSITE_OP};
 
   #[test]
   fn test_get_add_and_muls() {
@@ -297,6 +265,51 @@ mod test {
     );
   }
 
+  // sumcheck over one layer's composite, checked the way the GKR verifier checks it: the claimed sum is the
+  // output layer's value at r, and the final round sum is the layer polynomial opened at the challenges
+  #[test]
+  fn test_layer_composite_sumcheck() {
+    let gates = vec![
+      vec![Gate::new(0, 1, CIRCUIT_OP::ADD, 0)],
+      vec![
+        Gate::new(0, 1, CIRCUIT_OP::ADD, 0),
+        Gate::new(2, 3, CIRCUIT_OP::MUL, 1),
+      ],
+    ];
+    let mut circuit: Circuit<Fq> = Circuit::new(gates);
+    let inputs: Vec<Fq> = vec![1, 2, 3, 4].iter().map(|x| Fq::from(*x)).collect();
+    circuit.evaluate(&inputs);
+    assert_eq!(circuit.layers[0], vec![Fq::from(15)]);
+
+    // the single output is padded to one variable
+    let w_0 = MultivariatePoly::from_coeffs(vec![Fq::from(15), Fq::from(0)]);
+    let w_1 = MultivariatePoly::from_coeffs(circuit.layers[1].clone());
+    let blows = circuit.gkr_layout(0).bits_per_index as u32;
+
+    for r in [Fq::from(0), Fq::from(7)] {
+      let add_poly = circuit.addi(0).solve(&vec![Some(r)]);
+      let mul_poly = circuit.muli(0).solve(&vec![Some(r)]);
+      let f_poly = layer_composite(add_poly.clone(), mul_poly.clone(), &w_1, blows);
+
+      let mut round_polys = vec![];
+      let mut challenges = vec![];
+      let mut prover_transcript = Transcript::new(KeccakWrapper { keccak: Keccak256::new() });
+      let sum = generate_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&f_poly, &mut prover_transcript, &mut round_polys, &mut challenges);
+      assert_eq!(sum, w_0.evaluate(&[r]));
+
+      let polys: Vec<Vec<Fq>> = round_polys.iter().map(|p| p.coefficients.clone()).collect();
+      let mut verifier_transcript = Transcript::new(KeccakWrapper { keccak: Keccak256::new() });
+      let (final_sum, verifier_challenges, success) =
+        verify_partial_proof_2::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(sum, &polys, &mut verifier_transcript);
+      assert!(success);
+      assert_eq!(verifier_challenges, challenges);
+
+      let (w_b_eval, w_c_eval) = (w_1.fold_all(&challenges[..blows as usize]), w_1.fold_all(&challenges[blows as usize..]));
+      let opened = add_poly.fold_all(&challenges) * (w_b_eval + w_c_eval) + mul_poly.fold_all(&challenges) * (w_b_eval * w_c_eval);
+      assert_eq!(final_sum, opened);
+    }
+  }
+
   // 4b + 2a
   #[test]
   fn test_apply_alpha_beta() {
@@ -310,7 +323,12 @@ mod test {
     assert_eq!(
       new_poly.coeffs,
       vec![26, 46, 41, 61].iter().map(|x| Fq::from(*x)).collect::<Vec<Fq>>()
-    )
+    );
+
+    // matches the allocating operator version
+    let first = poly.solve(&vec![Some(Fq::from(2)), None, None]);
+    let second = poly.solve(&vec![Some(Fq::from(3)), None, None]);
+    assert_eq!(new_poly, first.scalar_mul(Fq::from(2)) + second.scalar_mul(Fq::from(3)));
   }
 
   #[test]
@@ -339,14 +357,152 @@ mod test {
     let inputs: Vec<Fq> = vec![ 1, 2, 3, 4, 5, 6, 7, 8 ].iter().map(|x| Fq::from(*x)).collect();
     
     let mut hasher = KeccakWrapper { keccak: Keccak256::new() };
-    let mut transcript = Transcript::new(hasher);
-    let gkr_proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&mut circuit, &inputs, &mut transcript);
+    let mut prover_transcript = TranscriptProtocol::new(Transcript::new(hasher));
+    let gkr_proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&mut circuit, &inputs, &mut prover_transcript);
     
     hasher = KeccakWrapper { keccak: Keccak256::new() };
-    transcript = Transcript::new(hasher);
+    let mut verifier_transcript = TranscriptProtocol::new(Transcript::new(hasher));
     assert_eq!(
       true, 
-      verify_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&mut circuit, &inputs, &mut transcript, gkr_proof)
+      verify_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&mut circuit, &inputs, &mut verifier_transcript, gkr_proof)
     );
+
+    prover_transcript.assert_same_phases(&verifier_transcript);
+    if cfg!(debug_assertions) {
+      let phases = prover_transcript.phases();
+      assert_eq!(phases[..3], [Phase::AbsorbCircuit, Phase::AbsorbOutput, Phase::SqueezeInitialChallenges]);
+      // one alpha/beta squeeze for every layer after the first
+      assert_eq!(phases.iter().filter(|p| **p == Phase::SqueezeAlphaBeta).count(), 2);
+      assert_eq!(phases.iter().filter(|p| **p == Phase::LayerLabel).count(), 3);
+      assert_eq!(phases.iter().filter(|p| **p == Phase::AbsorbEvaluations).count(), 3);
+    }
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn test_proof_is_bound_to_its_circuit() {
+    // B swaps the operands of A's input-layer gates: the same values on every layer, so without the
+    // circuit in the transcript both would draw the same output challenges and send the same first layer
+    let circuit = |swapped: bool| -> Circuit<Fq> {
+      let (add, mul) = if swapped { ((1, 0), (3, 2)) } else { ((0, 1), (2, 3)) };
+      Circuit::new(vec![
+        vec![Gate::new(0, 1, CIRCUIT_OP::ADD, 0)],
+        vec![Gate::new(add.0, add.1, CIRCUIT_OP::ADD, 0), Gate::new(mul.0, mul.1, CIRCUIT_OP::MUL, 1)],
+      ])
+    };
+    let inputs: Vec<Fq> = (1..=4).map(|x| Fq::from(x as u64)).collect();
+    let new_transcript = || TranscriptProtocol::new(Transcript::new(KeccakWrapper { keccak: Keccak256::new() }));
+
+    let (mut circuit_a, mut circuit_b) = (circuit(false), circuit(true));
+    assert_ne!(circuit_a.structure_bytes(), circuit_b.structure_bytes());
+    let prove = |circuit: &mut Circuit<Fq>| {
+      generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(circuit, &inputs, &mut new_transcript())
+    };
+    let (proof_a, proof_b) = (prove(&mut circuit_a), prove(&mut circuit_b));
+    assert_eq!(circuit_a.layers, circuit_b.layers);
+
+    // the output layer's sumcheck only differs through the challenges the circuit bytes fed into
+    assert_ne!(proof_a.claimed_sums[0], proof_b.claimed_sums[0]);
+    assert_ne!(proof_a.round_polys[0], proof_b.round_polys[0]);
+
+    // a failed round check inside the sumcheck verifier panics rather than returning false
+    let verifies = |circuit: &mut Circuit<Fq>, proof: GKR_PROOF<Fq>| {
+      std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        verify_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(circuit, &inputs, &mut new_transcript(), proof)
+      }))
+      .unwrap_or(false)
+    };
+    assert!(!verifies(&mut circuit_b, proof_a));
+    assert!(!verifies(&mut circuit_a, proof_b));
+    let (proof_a, proof_b) = (prove(&mut circuit_a), prove(&mut circuit_b));
+    assert!(verifies(&mut circuit_a, proof_a));
+    assert!(verifies(&mut circuit_b, proof_b));
+  }
+
+  #[test]
+  fn test_layer_poly_tree_matches_flat_composite() {
+    let gates = vec![
+      vec![Gate::new(0, 1, CIRCUIT_OP::ADD, 0), Gate::new(2, 3, CIRCUIT_OP::MUL, 1)],
+      vec![
+        Gate::new(0, 1, CIRCUIT_OP::ADD, 0),
+        Gate::new(2, 3, CIRCUIT_OP::MUL, 1),
+        Gate::new(4, 5, CIRCUIT_OP::MUL, 2),
+        Gate::new(6, 7, CIRCUIT_OP::ADD, 3),
+      ],
+    ];
+    let mut circuit: Circuit<Fq> = Circuit::new(gates);
+    circuit.evaluate(&(1..=8).map(|x| Fq::from(x as u64)).collect());
+
+    let r = vec![Some(Fq::from(5))];
+    let add_poly = circuit.addi(0).solve(&r);
+    let mul_poly = circuit.muli(0).solve(&r);
+    let w = MultivariatePoly::new(circuit.layers[1].clone(), 2);
+    let (w_b, w_c) = (w.blow_up_right(2), w.blow_up_left(2));
+
+    // the same polynomial with w(b) + w(c) and w(b)·w(c) precomputed into single tables
+    let flat = Composite::new(
+      &vec![add_poly.coeffs.clone(), (w_b.clone() + w_c.clone()).coeffs, mul_poly.coeffs.clone(), (w_b.clone() * w_c.clone()).coeffs],
+      vec![COMPOSITE_OP::MUL, COMPOSITE_OP::ADD, COMPOSITE_OP::MUL],
+    );
+    let tree = layer_poly_tree(add_poly, mul_poly, w_b, w_c);
+
+    assert_eq!(tree.reduce(), flat.reduce());
+    let point: Vec<Fq> = (0..4).map(|x| Fq::from(x as u64 + 7)).collect();
+    assert_eq!(tree.evaluate(&point), flat.evaluate(&point.iter().map(|x| Some(*x)).collect()));
+
+    // sumcheck over either form proves the same sum, and the tree's final round lands on the tree opened at the
+    // challenges; the round polynomials themselves differ since the tree counts w(b)·w(c) as degree 2
+    let mut sums = vec![];
+    for (sum, round_polys, challenges) in [sumcheck_layer(&tree), sumcheck_layer(&flat)] {
+      let polys: Vec<Vec<Fq>> = round_polys.iter().map(|p| p.coefficients.clone()).collect();
+      let mut verifier_transcript = Transcript::new(KeccakWrapper { keccak: Keccak256::new() });
+      let (final_sum, verifier_challenges, success) =
+        verify_partial_proof_2::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(sum, &polys, &mut verifier_transcript);
+      assert!(success);
+      assert_eq!(verifier_challenges, challenges);
+      assert_eq!(final_sum, tree.evaluate(&challenges));
+      sums.push(sum);
+    }
+    assert_eq!(sums[0], sums[1]);
+  }
+
+  fn sumcheck_layer(poly: &impl SumcheckPoly<Fq>) -> (Fq, Vec<DensePolynomial<Fq>>, Vec<Fq>) {
+    let mut round_polys = vec![];
+    let mut challenges = vec![];
+    let mut transcript = Transcript::new(KeccakWrapper { keccak: Keccak256::new() });
+    let sum = generate_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(poly, &mut transcript, &mut round_polys, &mut challenges);
+    (sum, round_polys, challenges)
+  }
+
+  // random GKR-shaped circuit of `depth` gate layers over 2^depth inputs, halving in width up to a single output
+  fn random_circuit(depth: usize, rng: &mut StdRng) -> Circuit<Fq> {
+    let widths: Vec<usize> = (0..depth).rev().map(|layer| 1 << layer).collect();
+    let positional = circuit::circuit::Circuit::random_layered(1 << depth, &widths, rng);
+    Circuit::try_from(&positional).unwrap()
+  }
+
+  // Timings for prove/verify over growing circuits; run with `cargo test --release -- --ignored --nocapture`
+  // to see them. Only verification is asserted, the times are too close to compare. Depth 12 is a known gap
+  // (see the README): the dense wiring polynomials would need 2^35 entries.
+  #[test]
+  #[ignore]
+  fn test_prove_verify_scaling() {
+    let mut rng = StdRng::seed_from_u64(2250);
+    for depth in [4, 8] {
+      let mut circuit = random_circuit(depth, &mut rng);
+      let inputs: Vec<Fq> = (1..=(1u64 << depth)).map(Fq::from).collect();
+
+      let mut prover_transcript = TranscriptProtocol::new(Transcript::new(KeccakWrapper { keccak: Keccak256::new() }));
+      let start = std::time::Instant::now();
+      let gkr_proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&mut circuit, &inputs, &mut prover_transcript);
+      let prove_time = start.elapsed();
+
+      let mut verifier_transcript = TranscriptProtocol::new(Transcript::new(KeccakWrapper { keccak: Keccak256::new() }));
+      let start = std::time::Instant::now();
+      let verified = verify_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&mut circuit, &inputs, &mut verifier_transcript, gkr_proof);
+      let verify_time = start.elapsed();
+
+      println!("depth {depth}: prove {prove_time:?}, verify {verify_time:?}");
+      assert!(verified);
+    }
+  }
+}
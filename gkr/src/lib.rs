@@ -1,2 +1,3 @@
-mod circut;
-mod gkr;
\ No newline at end of file
+pub mod circut;
+mod gkr;
+mod protocol;
\ No newline at end of file
@@ -0,0 +1,141 @@
+use ark_ff::PrimeField;
+use transcript::transcript::TranscriptTrait;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Phase {
+  AbsorbCircuit,
+  AbsorbOutput,
+  LayerLabel,
+  SqueezeInitialChallenges,
+  SqueezeAlphaBeta,
+  // absorbs and squeezes made by the sumcheck rounds through `TranscriptTrait`
+  AbsorbRound,
+  SqueezeRound,
+  AbsorbEvaluations,
+}
+
+// Transcript wrapper for the GKR prover and verifier. Each step of the protocol goes through a named
+// method, and debug builds record the sequence of phases so both sides can be checked against each other.
+pub(crate) struct TranscriptProtocol<F: PrimeField, T: TranscriptTrait<F>> {
+  transcript: T,
+  phases: Vec<Phase>,
+  _field: PhantomData<F>,
+}
+
+impl<F: PrimeField, T: TranscriptTrait<F>> TranscriptProtocol<F, T> {
+  pub(crate) fn new(transcript: T) -> Self {
+    TranscriptProtocol { transcript, phases: vec![], _field: PhantomData }
+  }
+
+  fn record(&mut self, phase: Phase) {
+    if cfg!(debug_assertions) {
+      self.phases.push(phase);
+    }
+  }
+
+  // binds the proof to one circuit, so it can't be replayed against another with the same output
+  pub(crate) fn absorb_circuit(&mut self, structure: &[u8]) {
+    self.record(Phase::AbsorbCircuit);
+    let mut label = b"circuit".to_vec();
+    label.extend(structure);
+    self.transcript.absorb(&label);
+  }
+
+  pub(crate) fn absorb_output(&mut self, output: &[F]) {
+    self.record(Phase::AbsorbOutput);
+    self.transcript.absorb_field_slice(output);
+  }
+
+  pub(crate) fn squeeze_initial_challenges(&mut self, n: usize) -> Vec<F> {
+    self.record(Phase::SqueezeInitialChallenges);
+    self.transcript.squeeze_n(n)
+  }
+
+  pub(crate) fn squeeze_alpha_beta(&mut self) -> (F, F) {
+    self.record(Phase::SqueezeAlphaBeta);
+    let draws = self.transcript.squeeze_n(2);
+    (draws[0], draws[1])
+  }
+
+  pub(crate) fn absorb_evaluations(&mut self, w_b_eval: F, w_c_eval: F) {
+    self.record(Phase::AbsorbEvaluations);
+    self.transcript.absorb_field_slice(&[w_b_eval, w_c_eval]);
+  }
+
+  // absorbs a `"layer" || index` label before running `f`, so two layers producing the same round
+  // data still draw different challenges
+  pub(crate) fn with_layer(&mut self, layer_index: usize, f: impl FnOnce(&mut Self)) {
+    self.record(Phase::LayerLabel);
+    let mut label = b"layer".to_vec();
+    label.extend((layer_index as u64).to_be_bytes());
+    self.transcript.absorb(&label);
+    f(self);
+  }
+
+  pub(crate) fn phases(&self) -> &[Phase] {
+    &self.phases
+  }
+
+  // called once both sides are done; any difference means they derived different challenges
+  pub(crate) fn assert_same_phases(&self, other: &Self) {
+    debug_assert_eq!(self.phases, other.phases, "prover and verifier transcript phases diverged");
+  }
+}
+
+impl<F: PrimeField, T: TranscriptTrait<F>> TranscriptTrait<F> for TranscriptProtocol<F, T> {
+  fn absorb(&mut self, data: &[u8]) {
+    self.record(Phase::AbsorbRound);
+    self.transcript.absorb(data);
+  }
+
+  fn squeeze(&mut self) -> F {
+    self.record(Phase::SqueezeRound);
+    self.transcript.squeeze()
+  }
+
+  fn generate_challenge(&mut self) -> F {
+    self.squeeze()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use ark_bn254::Fq;
+  use sha3::{Keccak256, Digest};
+  use transcript::transcript::{KeccakWrapper, Transcript};
+
+  fn new_protocol() -> TranscriptProtocol<Fq, Transcript<KeccakWrapper, Fq>> {
+    TranscriptProtocol::new(Transcript::new(KeccakWrapper { keccak: Keccak256::new() }))
+  }
+
+  // the same round data absorbed under a given layer index
+  fn layer_challenge(layer_index: usize) -> Fq {
+    let mut transcript = new_protocol();
+    transcript.absorb_output(&[Fq::from(3u64), Fq::from(5u64)]);
+    let mut challenge = Fq::from(0u64);
+    transcript.with_layer(layer_index, |transcript| {
+      transcript.absorb_evaluations(Fq::from(7u64), Fq::from(11u64));
+      challenge = transcript.challenge();
+    });
+    challenge
+  }
+
+  #[test]
+  fn test_squeezed_challenges_are_distinct() {
+    let mut transcript = new_protocol();
+    transcript.absorb_output(&[Fq::from(3u64), Fq::from(5u64)]);
+    let initial = transcript.squeeze_initial_challenges(3);
+    assert!(initial[0] != initial[1] && initial[1] != initial[2] && initial[0] != initial[2]);
+    let (alpha, beta) = transcript.squeeze_alpha_beta();
+    assert_ne!(alpha, beta);
+  }
+
+  #[test]
+  fn test_with_layer_separates_identical_layers() {
+    assert_eq!(layer_challenge(1), layer_challenge(1));
+    assert_ne!(layer_challenge(1), layer_challenge(2));
+    assert_ne!(layer_challenge(0), layer_challenge(1));
+  }
+}
@@ -1,7 +1,7 @@
 use core::panic;
 
 use ark_ff::PrimeField;
-use crate::multilinear::MultivariatePoly;
+use crate::multilinear::{MultivariatePoly, VarOrder};
 use std::ops::{Add, Mul};
 
 #[derive(Clone, Debug)]
@@ -83,11 +83,8 @@ impl <F: PrimeField> Composite<F> {
     
         let polys = hypercubes.iter()
         .map(|cube| {
-            let num_vars = (cube.len() as f64).log2() as usize;
-            if 2usize.pow(num_vars as u32) != cube.len() {
-                panic!("Hypercube len = {}, but expected a power of 2", cube.len());
-            }
-            MultivariatePoly::new(cube.to_vec(), num_vars)
+            MultivariatePoly::try_from_coeffs(cube.to_vec())
+                .unwrap_or_else(|_| panic!("Hypercube len = {}, but expected a power of 2", cube.len()))
         })
         .collect();
         Composite {
@@ -107,7 +104,7 @@ pub fn partial_evaluate(&self, value: &Vec<F>, index: usize) -> Self {
             .iter()
             .map(|x| {
                 // Call partial_evaluate and wrap the result in a MultivariatePoly
-                let new_coeffs = MultivariatePoly::partial_evaluate(&x.coeffs, index, value[0]);
+                let new_coeffs = MultivariatePoly::partial_evaluate_ordered(&x.coeffs, index, value[0], VarOrder::FromHigh);
                 MultivariatePoly::new(new_coeffs, x.num_vars - 1) // Reduce the number of variables by 1
             })
             .collect(),
@@ -134,7 +131,7 @@ pub fn evaluate(&self, values: &Vec<Option<F>>) -> F {
                 .map(|v| v.expect("All values should be provided")) 
                 .collect();
 
-                let eval_result = poly.evaluate_partial(&evaluated_values); // Use evaluate instead of sum_over_boolean_hypercube
+                let eval_result = poly.fold_all(&evaluated_values);
                 // println!("Evaluated poly: {:?}", eval_result); // Debug each polynomial evaluation
 
             OP_ELEMENT::Value(eval_result)
@@ -166,6 +163,177 @@ pub fn reduce (&self) -> MultivariatePoly<F> {
     panic!("Failed to evaluate to a multinear");
   }
 
+// Degree in each variable. Every poly is multilinear, so a product of k of them has degree k and a sum
+// takes the largest degree of its terms; with MUL binding tighter than ADD that's the longest MUL run + 1.
+pub fn degree(&self) -> usize {
+    let mut degree = 1;
+    let mut run = 1;
+    for op in &self.ops {
+      match op {
+        OP::MUL => run += 1,
+        OP::ADD => run = 1,
+      }
+      degree = degree.max(run);
+    }
+    degree
+  }
+
+}
+
+// A composite as an explicit expression tree, e.g. the GKR layer polynomial add·(w_b + w_c) + mul·(w_b·w_c),
+// instead of a flat op list that relies on MUL binding tighter than ADD
+#[derive(Clone, Debug)]
+pub enum CompositeTree<F: PrimeField> {
+  Leaf(MultivariatePoly<F>),
+  Add(Box<CompositeTree<F>>, Box<CompositeTree<F>>),
+  Mul(Box<CompositeTree<F>>, Box<CompositeTree<F>>),
+}
+
+impl <F: PrimeField> CompositeTree<F> {
+    // Entry-wise over the hypercube, like `Composite::reduce`
+    pub fn reduce(&self) -> MultivariatePoly<F> {
+      match self {
+        CompositeTree::Leaf(poly) => poly.clone(),
+        CompositeTree::Add(left, right) => left.reduce() + right.reduce(),
+        CompositeTree::Mul(left, right) => left.reduce() * right.reduce(),
+      }
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> F {
+      match self {
+        CompositeTree::Leaf(poly) => poly.evaluate(point),
+        CompositeTree::Add(left, right) => left.evaluate(point) + right.evaluate(point),
+        CompositeTree::Mul(left, right) => left.evaluate(point) * right.evaluate(point),
+      }
+    }
+
+    // Degree in each variable: leaves are multilinear, a sum takes the larger degree and a product adds them
+    pub fn degree(&self) -> usize {
+      match self {
+        CompositeTree::Leaf(_) => 1,
+        CompositeTree::Add(left, right) => left.degree().max(right.degree()),
+        CompositeTree::Mul(left, right) => left.degree() + right.degree(),
+      }
+    }
+
+    pub fn num_vars(&self) -> usize {
+      match self {
+        CompositeTree::Leaf(poly) => poly.num_vars,
+        CompositeTree::Add(left, _) | CompositeTree::Mul(left, _) => left.num_vars(),
+      }
+    }
+
+    // Binds variable `index` of every leaf to `value`, keeping the shape of the tree
+    pub fn partial_evaluate(&self, value: F, index: usize) -> Self {
+      match self {
+        CompositeTree::Leaf(poly) => CompositeTree::Leaf(MultivariatePoly::new(
+          MultivariatePoly::partial_evaluate_ordered(&poly.coeffs, index, value, VarOrder::FromHigh),
+          poly.num_vars - 1,
+        )),
+        CompositeTree::Add(left, right) => left.partial_evaluate(value, index) + right.partial_evaluate(value, index),
+        CompositeTree::Mul(left, right) => left.partial_evaluate(value, index) * right.partial_evaluate(value, index),
+      }
+    }
+}
+
+// What the sumcheck prover needs from a combination of multilinear tables, so it runs the same way over a
+// flat `Composite` and a `CompositeTree`
+pub trait SumcheckPoly<F: PrimeField>: Clone {
+    fn num_vars(&self) -> usize;
+    // degree in each variable, which fixes how many evaluations a round polynomial needs
+    fn degree(&self) -> usize;
+    // the combination entry-wise over the hypercube
+    fn reduce(&self) -> MultivariatePoly<F>;
+    fn evaluate_at(&self, point: &[F]) -> F;
+    // binds the first remaining variable
+    fn bind_first(&self, value: F) -> Self;
+}
+
+impl <F: PrimeField> SumcheckPoly<F> for Composite<F> {
+    fn num_vars(&self) -> usize {
+      self.polys[0].num_vars
+    }
+
+    fn degree(&self) -> usize {
+      Composite::degree(self)
+    }
+
+    fn reduce(&self) -> MultivariatePoly<F> {
+      Composite::reduce(self)
+    }
+
+    fn evaluate_at(&self, point: &[F]) -> F {
+      self.evaluate(&point.iter().map(|x| Some(*x)).collect())
+    }
+
+    fn bind_first(&self, value: F) -> Self {
+      self.partial_evaluate(&vec![value], 0)
+    }
+}
+
+impl <F: PrimeField> SumcheckPoly<F> for CompositeTree<F> {
+    fn num_vars(&self) -> usize {
+      CompositeTree::num_vars(self)
+    }
+
+    fn degree(&self) -> usize {
+      CompositeTree::degree(self)
+    }
+
+    fn reduce(&self) -> MultivariatePoly<F> {
+      CompositeTree::reduce(self)
+    }
+
+    fn evaluate_at(&self, point: &[F]) -> F {
+      self.evaluate(point)
+    }
+
+    fn bind_first(&self, value: F) -> Self {
+      self.partial_evaluate(value, 0)
+    }
+}
+
+// `a + b` and `a * b` build the tree nodes rather than computing anything
+impl <F: PrimeField> Add for CompositeTree<F> {
+    type Output = CompositeTree<F>;
+
+    fn add(self, other: CompositeTree<F>) -> CompositeTree<F> {
+      CompositeTree::Add(Box::new(self), Box::new(other))
+    }
+}
+
+impl <F: PrimeField> Mul for CompositeTree<F> {
+    type Output = CompositeTree<F>;
+
+    fn mul(self, other: CompositeTree<F>) -> CompositeTree<F> {
+      CompositeTree::Mul(Box::new(self), Box::new(other))
+    }
+}
+
+// Groups the flat list the way `shunting_yard_algo` evaluates it: each run of MULs becomes a left-leaning
+// product, and the products are summed left to right
+impl <F: PrimeField> From<&Composite<F>> for CompositeTree<F> {
+    fn from(composite: &Composite<F>) -> Self {
+      let mut polys = composite.polys.iter().cloned().map(CompositeTree::Leaf);
+      let mut sum: Option<CompositeTree<F>> = None;
+      let mut product = polys.next().expect("a composite has at least one poly");
+      for (op, poly) in composite.ops.iter().zip(polys) {
+        match op {
+          OP::MUL => product = product * poly,
+          OP::ADD => {
+            sum = Some(match sum {
+              Some(sum) => sum + product,
+              None => product,
+            });
+            product = poly;
+          }
+        }
+      }
+      match sum {
+        Some(sum) => sum + product,
+        None => product,
+      }
+    }
 }
 
 fn get_op<F: PrimeField> (list: &Vec<OP_ELEMENT<F>>, index: usize) -> OP{
@@ -384,6 +552,48 @@ fn test_partial_evaluate_another_new_example() {
   }
 
 
+  #[test]
+  fn test_tree_matches_flat() {
+    let polys: Vec<Vec<Fq>> = [[0, 3, 2, 5], [0, 4, 0, 11], [0, 3, 6, 11], [1, 2, 3, 4], [5, 0, 7, 1]]
+      .iter()
+      .map(|poly| poly.iter().map(|x| Fq::from(*x)).collect())
+      .collect();
+    let point = [Fq::from(2), Fq::from(9)];
+
+    for ops in [
+      vec![OP::MUL, OP::ADD, OP::MUL, OP::MUL],
+      vec![OP::ADD, OP::MUL, OP::MUL, OP::ADD],
+      vec![OP::ADD, OP::ADD, OP::ADD, OP::ADD],
+      vec![OP::MUL, OP::MUL, OP::MUL, OP::MUL],
+    ] {
+      let flat = Composite::new(&polys, ops);
+      let tree = CompositeTree::from(&flat);
+      assert_eq!(tree.reduce(), flat.reduce());
+      assert_eq!(tree.degree(), flat.degree());
+      assert_eq!(tree.evaluate(&point), flat.evaluate(&point.iter().map(|x| Some(*x)).collect()));
+    }
+  }
+
+  #[test]
+  fn test_tree_degree() {
+    let leaf = || CompositeTree::Leaf(MultivariatePoly::new(vec![Fq::from(1), Fq::from(2)], 1));
+    // a·(b + c) is quadratic, a·(b·c) + d cubic
+    assert_eq!((leaf() * (leaf() + leaf())).degree(), 2);
+    assert_eq!((leaf() * (leaf() * leaf()) + leaf()).degree(), 3);
+  }
+
+  #[test]
+  fn test_degree() {
+    let poly: Vec<Fq> = [0, 3, 2, 5].iter().map(|x| Fq::from(*x)).collect();
+    let composite = |ops: Vec<OP>| Composite::new(&vec![poly.clone(); ops.len() + 1], ops);
+
+    assert_eq!(composite(vec![]).degree(), 1);
+    assert_eq!(composite(vec![OP::ADD, OP::ADD]).degree(), 1);
+    assert_eq!(composite(vec![OP::MUL, OP::ADD, OP::MUL]).degree(), 2);
+    assert_eq!(composite(vec![OP::MUL, OP::MUL, OP::ADD]).degree(), 3);
+    assert_eq!(composite(vec![OP::ADD, OP::MUL, OP::MUL, OP::MUL]).degree(), 4);
+  }
+
   #[test]
   fn test_reduce() {
     let poly_a = vec![0, 3, 2, 5].iter().map(|x| Fq::from(x.clone())).collect();
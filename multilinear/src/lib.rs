@@ -1,2 +1,4 @@
 pub mod multilinear;
-pub mod composite;
\ No newline at end of file
+pub mod composite;
+pub mod monomial;
+pub mod sparse;
\ No newline at end of file
@@ -0,0 +1,120 @@
+use ark_ff::PrimeField;
+use crate::multilinear::MultivariatePoly;
+
+// A multilinear polynomial in the monomial basis: `coeffs[mask]` is the coefficient of the product
+// of the variables whose bits are set in `mask`, MSB first, so [1, 2, 3, 4] is 1 + 3x_0 + 2x_1 + 4x_0x_1.
+// `MultivariatePoly` instead stores evaluations over the boolean hypercube; both use the same
+// bit-to-variable mapping, and converting between them is the Möbius/zeta transform.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonomialPoly<F: PrimeField> {
+    pub coeffs: Vec<F>,
+    pub num_vars: usize,
+}
+
+impl<F: PrimeField> MonomialPoly<F> {
+    pub fn new(coeffs: Vec<F>, num_vars: usize) -> Self {
+        if coeffs.len() != 1 << num_vars {
+            panic!("Invalid number of coefficients");
+        }
+        Self { coeffs, num_vars }
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> F {
+        if point.len() != self.num_vars {
+            panic!("Invalid number of variables");
+        }
+        self.coeffs
+            .iter()
+            .enumerate()
+            .map(|(mask, &coeff)| {
+                (0..self.num_vars)
+                    .filter(|j| (mask >> (self.num_vars - 1 - j)) & 1 == 1)
+                    .fold(coeff, |term, j| term * point[j])
+            })
+            .sum()
+    }
+}
+
+// Möbius transform: evaluations over the hypercube -> monomial coefficients
+impl<F: PrimeField> From<MultivariatePoly<F>> for MonomialPoly<F> {
+    fn from(poly: MultivariatePoly<F>) -> Self {
+        let mut coeffs = poly.coeffs;
+        for j in 0..poly.num_vars {
+            for mask in 0..coeffs.len() {
+                if (mask >> j) & 1 == 1 {
+                    let lower = coeffs[mask ^ (1 << j)];
+                    coeffs[mask] -= lower;
+                }
+            }
+        }
+        MonomialPoly::new(coeffs, poly.num_vars)
+    }
+}
+
+// Zeta transform: monomial coefficients -> evaluations over the hypercube
+impl<F: PrimeField> From<MonomialPoly<F>> for MultivariatePoly<F> {
+    fn from(poly: MonomialPoly<F>) -> Self {
+        let mut coeffs = poly.coeffs;
+        for j in 0..poly.num_vars {
+            for mask in 0..coeffs.len() {
+                if (mask >> j) & 1 == 1 {
+                    let lower = coeffs[mask ^ (1 << j)];
+                    coeffs[mask] += lower;
+                }
+            }
+        }
+        MultivariatePoly::new(coeffs, poly.num_vars)
+    }
+}
+
+// Two polynomials are equal when they represent the same function, whatever their basis
+impl<F: PrimeField> PartialEq<MonomialPoly<F>> for MultivariatePoly<F> {
+    fn eq(&self, other: &MonomialPoly<F>) -> bool {
+        let converted = MultivariatePoly::from(other.clone());
+        self.num_vars == converted.num_vars && self.coeffs == converted.coeffs
+    }
+}
+
+impl<F: PrimeField> PartialEq<MultivariatePoly<F>> for MonomialPoly<F> {
+    fn eq(&self, other: &MultivariatePoly<F>) -> bool {
+        other == self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+
+    #[test]
+    fn test_monomial_to_evaluations() {
+        // 1 + 3x_0 + 2x_1 + 4x_0x_1 over (0,0), (0,1), (1,0), (1,1)
+        let monomial = MonomialPoly::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)], 2);
+        let mle = MultivariatePoly::from(monomial.clone());
+        assert_eq!(mle.coeffs, vec![Fr::from(1u64), Fr::from(3u64), Fr::from(4u64), Fr::from(10u64)]);
+        assert!(mle == monomial);
+        assert!(monomial == mle);
+    }
+
+    #[test]
+    fn test_round_trip_evaluates_identically() {
+        let mut rng = ark_std::test_rng();
+        let coeffs: Vec<Fr> = (0..16).map(|_| Fr::rand(&mut rng)).collect();
+        let mle = MultivariatePoly::new(coeffs, 4);
+        let monomial = MonomialPoly::from(mle.clone());
+
+        assert_eq!(MultivariatePoly::from(monomial.clone()), mle);
+        for _ in 0..5 {
+            let point: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+            assert_eq!(monomial.evaluate(&point), mle.evaluate(&point));
+        }
+    }
+
+    #[test]
+    fn test_different_functions_are_not_equal() {
+        let mle = MultivariatePoly::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)], 2);
+        let monomial = MonomialPoly::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)], 2);
+        assert!(mle != monomial);
+    }
+}
@@ -1,9 +1,35 @@
 use ark_ff::PrimeField;
 use ark_bn254::Fr;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate, Write};
+use ark_std::rand::Rng;
 use rand::thread_rng;
-use std::ops::{Add, Mul};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum PolyError {
+    // `index` does not name one of the polynomial's `num_vars` variables
+    InvalidVariableIndex { index: usize, num_vars: usize },
+    // shrinking from `num_vars` to `target` would drop non-zero evaluations
+    LossyShrink { num_vars: usize, target: usize },
+    // `got` evaluations where `num_vars` variables need `expected = 2^num_vars`
+    WrongLength { expected: usize, got: usize },
+    // an evaluation table whose length isn't a power of two, so it fits no number of variables
+    NotPowerOfTwo { len: usize },
+}
+
+// Which end of the table index a variable position counts from in `partial_evaluate_ordered`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VarOrder {
+    // position 0 is the most significant bit, i.e. variable 0 in the MSB-first convention below
+    FromHigh,
+    // position 0 is the least significant bit, i.e. the last variable
+    FromLow,
+}
+
+// Evaluations over the boolean hypercube, MSB first: variable 0 is the most significant bit of the table
+// index and variable `num_vars - 1` the least, so with two variables coeffs[0b10] is f(1, 0). `evaluate`,
+// `partial_evaluate`, `fold_all` and the GKR wiring layout all read the index this way.
 #[derive(Clone, Debug, PartialEq)]
 pub struct MultivariatePoly<F: PrimeField> {
     pub coeffs: Vec<F>,
@@ -12,57 +38,144 @@ pub struct MultivariatePoly<F: PrimeField> {
 
 impl<F: PrimeField> MultivariatePoly<F> {
     pub fn new(coeffs: Vec<F>, num_vars: usize) -> Self {
-        if coeffs.len() != 2usize.pow(num_vars as u32) {
-            panic!("Invalid number of coefficients");
+        Self::try_new(coeffs, num_vars).expect("Invalid number of coefficients")
+    }
+
+    // Same as `new`, but returns an error instead of panicking when there aren't `2^num_vars` coefficients
+    pub fn try_new(coeffs: Vec<F>, num_vars: usize) -> Result<Self, PolyError> {
+        let expected = 1usize << num_vars;
+        if coeffs.len() != expected {
+            return Err(PolyError::WrongLength { expected, got: coeffs.len() });
+        }
+        Ok(Self { coeffs, num_vars })
+    }
+
+    // Takes `num_vars` from the table length, which must be a power of two
+    pub fn from_coeffs(coeffs: Vec<F>) -> Self {
+        let len = coeffs.len();
+        Self::try_from_coeffs(coeffs).unwrap_or_else(|_| panic!("Coefficient count {} is not a power of two", len))
+    }
+
+    pub fn try_from_coeffs(coeffs: Vec<F>) -> Result<Self, PolyError> {
+        if !coeffs.len().is_power_of_two() {
+            return Err(PolyError::NotPowerOfTwo { len: coeffs.len() });
         }
-        Self { coeffs, num_vars }
+        let num_vars = coeffs.len().trailing_zeros() as usize;
+        Ok(Self { coeffs, num_vars })
+    }
+
+    // Wraps a table taken out with `into_coeffs` again, without cloning it; `try_from_coeffs` under the
+    // name that reads right at those call sites
+    pub fn rewrap(coeffs: Vec<F>) -> Result<Self, PolyError> {
+        Self::try_from_coeffs(coeffs)
+    }
+
+    pub fn into_coeffs(self) -> Vec<F> {
+        self.coeffs
+    }
+
+    pub fn as_coeffs(&self) -> &[F] {
+        &self.coeffs
     }
 
-   
+    // Multilinear extension of a boolean predicate over the `2^num_vars` table indices:
+    // entry i is one where `f(i)` holds and zero otherwise
+    pub fn from_boolean_fn<Fun>(num_vars: usize, f: Fun) -> Self
+    where
+        Fun: Fn(usize) -> bool,
+    {
+        let coeffs = (0..1usize << num_vars)
+            .map(|i| if f(i) { F::one() } else { F::zero() })
+            .collect();
+        Self::new(coeffs, num_vars)
+    }
+
+    // `2^num_vars` uniformly random evaluations, for fuzzing the sumcheck and GKR code
+    pub fn rand<R: Rng>(num_vars: usize, rng: &mut R) -> Self {
+        let coeffs = (0..1usize << num_vars).map(|_| F::rand(rng)).collect();
+        Self::new(coeffs, num_vars)
+    }
+
+    // Changes the declared arity by zero-padding or truncating the evaluation table. The added variables come
+    // first in the binding order, and the polynomial is zero wherever any of them is one, so shrinking is
+    // only allowed when every dropped evaluation is zero.
+    pub fn with_num_vars(&self, target: usize) -> Result<Self, PolyError> {
+        let target_len = 1 << target;
+        if target < self.num_vars && self.coeffs[target_len..].iter().any(|x| !x.is_zero()) {
+            return Err(PolyError::LossyShrink { num_vars: self.num_vars, target });
+        }
+        let mut coeffs = self.coeffs.clone();
+        coeffs.resize(target_len, F::zero());
+        Ok(Self::new(coeffs, target))
+    }
+
+    #[deprecated(note = "the index counts from the most significant bit; say so with `partial_evaluate_ordered`")]
     pub fn partial_evaluate(poly: &Vec<F>, var_idx: usize, val: F) -> Vec<F> {
-        let poly_size = poly.len();
-        let new_poly_size = poly_size / 2;
-        let mut new_poly: Vec<F> = Vec::with_capacity(new_poly_size);
-
-        let mut i = 0;
-        let mut j = 0;
-
-        while i < new_poly_size {
-            let y1 = poly[j];
-            let num_vars = poly.len().ilog2() as usize;
-            let power = num_vars - 1 - var_idx;
-            let y2 = poly[j | (1 << power)];
-            new_poly.push(y1 + (val * (y2 - y1)));
-
-            i += 1;
-            j = if (j + 1) % (1 << power) == 0 {
-                j + 1 + (1 << power)
-            } else {
-                j + 1
-            }
+        Self::partial_evaluate_ordered(poly, var_idx, val, VarOrder::FromHigh)
+    }
+
+    // Binds the variable at `position`, counted from the end of the index `order` names, to `val`
+    pub fn partial_evaluate_ordered(poly: &[F], position: usize, val: F, order: VarOrder) -> Vec<F> {
+        let num_vars = poly.len().checked_ilog2().unwrap_or(0) as usize;
+        let var_idx = match order {
+            VarOrder::FromHigh => position,
+            VarOrder::FromLow => num_vars.checked_sub(position + 1).expect("Invalid variable index"),
+        };
+        Self::try_partial_evaluate(poly, var_idx, val).expect("Invalid variable index")
+    }
+
+    // Coefficient indices that differ only in variable `index`'s bit, as (bit clear, bit set), in
+    // ascending order. Variable 0 is the most significant bit, as in `partial_evaluate`.
+    pub fn pairs(index: usize, num_vars: usize) -> Vec<(usize, usize)> {
+        if index >= num_vars {
+            panic!("Invalid variable index");
+        }
+        let power = num_vars - 1 - index;
+        let low_mask = (1 << power) - 1;
+        (0..1usize << (num_vars - 1))
+            .map(|k| {
+                // insert a zero bit at `power`
+                let low = ((k & !low_mask) << 1) | (k & low_mask);
+                (low, low | (1 << power))
+            })
+            .collect()
+    }
+
+    // Same as `partial_evaluate`, but returns an error instead of underflowing when `var_idx` is out of range
+    pub fn try_partial_evaluate(poly: &[F], var_idx: usize, val: F) -> Result<Vec<F>, PolyError> {
+        let num_vars = poly.len().checked_ilog2().unwrap_or(0) as usize;
+        if var_idx >= num_vars {
+            return Err(PolyError::InvalidVariableIndex { index: var_idx, num_vars });
         }
 
-        new_poly
+        let new_poly = Self::pairs(var_idx, num_vars)
+            .into_iter()
+            .map(|(low, high)| poly[low] + val * (poly[high] - poly[low]))
+            .collect();
+
+        Ok(new_poly)
     }
 
 
-    pub fn evaluate(&self, point: &Vec<F>) -> F {
+    // Halves the table once per variable, 2^n work in total instead of a product of n factors per entry
+    pub fn evaluate(&self, point: &[F]) -> F {
         if point.len() != self.num_vars {
             panic!("Invalid number of variables");
         }
-        let mut result = F::zero();
-        for i in 0..self.coeffs.len() {
-            let mut term = self.coeffs[i];
-            for j in 0..self.num_vars {
-                if (i >> j) & 1 == 1 {
-                    term *= point[j]; // Multiply by x_j if the bit is set
-                } else {
-                    term *= F::one() - point[j]; // Multiply by (1 - x_j) if the bit is not set
-                }
-            }
-            result += term;
+        self.fold_all(point)
+    }
+
+    // The canonical full-binding reducer: binds the variables left to right, in `partial_evaluate`'s order,
+    // down to the single remaining coefficient
+    pub fn fold_all(&self, challenges: &[F]) -> F {
+        if challenges.len() != self.num_vars {
+            panic!("Invalid number of variables");
         }
-        result
+        let mut table = self.coeffs.clone();
+        for &challenge in challenges {
+            table = Self::partial_evaluate_ordered(&table, 0, challenge, VarOrder::FromHigh);
+        }
+        table[0]
     }
 
     pub fn evaluate_partial(&self, points: &Vec<F>) -> F {
@@ -70,7 +183,7 @@ impl<F: PrimeField> MultivariatePoly<F> {
         let num_points = points.len();
 
         for i in 0..num_points {
-            evaluated_poly = Self::partial_evaluate(&evaluated_poly, 0, points[i]);
+            evaluated_poly = Self::partial_evaluate_ordered(&evaluated_poly, 0, points[i], VarOrder::FromHigh);
         }
 
         evaluated_poly[0]
@@ -107,7 +220,11 @@ impl<F: PrimeField> MultivariatePoly<F> {
           let variable_len = hypercube.len().trailing_zeros() as usize;
           for (i, value) in values.iter().enumerate() {
             intermediate_result = match value {
-              Some(_value) => MultivariatePoly::new(MultivariatePoly::partial_evaluate(&intermediate_result.coeffs, 0, *_value), intermediate_result.num_vars - 1),
+              Some(_value) => {
+                let coeffs = MultivariatePoly::try_partial_evaluate(&intermediate_result.coeffs, 0, *_value)
+                    .expect("solve was given more values than the polynomial has variables");
+                MultivariatePoly::new(coeffs, intermediate_result.num_vars - 1)
+              },
               None => intermediate_result
             };
           }
@@ -117,6 +234,33 @@ impl<F: PrimeField> MultivariatePoly<F> {
       }
       
 
+    // Binds every `Some` variable at its own position and returns the polynomial over the `None` ones,
+    // which keep their relative order. Variables are bound from the last to the first so the indices
+    // still to be bound are not shifted.
+    pub fn evaluate_options(&self, point: &[Option<F>]) -> MultivariatePoly<F> {
+        if point.len() != self.num_vars {
+            panic!("Invalid number of variables");
+        }
+        let mut coeffs = self.coeffs.clone();
+        let mut num_vars = self.num_vars;
+        for (var_idx, value) in point.iter().enumerate().rev() {
+            if let Some(value) = value {
+                coeffs = Self::partial_evaluate_ordered(&coeffs, var_idx, *value, VarOrder::FromHigh);
+                num_vars -= 1;
+            }
+        }
+        MultivariatePoly::new(coeffs, num_vars)
+    }
+
+    // At a boolean point the value is just the table entry; bits[0] is the most significant bit of the index
+    pub fn evaluate_boolean(&self, bits: &[bool]) -> F {
+        if bits.len() != self.num_vars {
+            panic!("Invalid number of variables");
+        }
+        let index = bits.iter().fold(0, |index, &bit| (index << 1) | bit as usize);
+        self.coeffs[index]
+    }
+
     pub fn sum_over_boolean_hypercube(&self) -> F {
         let num_vars = self.num_vars; // Number of variables
         let num_points = 1 << num_vars; // 2^num_vars
@@ -126,15 +270,10 @@ impl<F: PrimeField> MultivariatePoly<F> {
         // Iterate over all points in the boolean hypercube
         for i in 0..num_points {
             // Create a point in the boolean hypercube
-            let mut point = vec![F::zero(); num_vars];
-            for j in 0..num_vars {
-                if (i >> j) & 1 == 1 {
-                    point[j] = F::one();
-                }
-            }
+            let point: Vec<bool> = (0..num_vars).map(|j| (i >> (num_vars - 1 - j)) & 1 == 1).collect();
     
             // Evaluate the polynomial at this point
-            let eval = self.evaluate(&point);
+            let eval = self.evaluate_boolean(&point);
             sum += eval;
         }
     
@@ -142,52 +281,99 @@ impl<F: PrimeField> MultivariatePoly<F> {
     }
 
 
+    // Binds the first `values.len()` variables (in the same order `evaluate` reads them) and returns
+    // the evaluation table over the remaining variables.
+    pub fn bind_prefix(&self, values: &[F]) -> Vec<F> {
+        let mut table = self.coeffs.clone();
+        for &value in values {
+            table = Self::partial_evaluate_ordered(&table, 0, value, VarOrder::FromHigh);
+        }
+        table
+    }
+
+    // Sum over the boolean hypercube of the variables after `round`, with the variables before it fixed to
+    // `partial_evaluation` and the round variable fixed to `x`.
     pub fn evaluate_at_round(&self, round: usize, partial_evaluation: &[F], x: F) -> F {
         let mut point = partial_evaluation[0..round].to_vec();
         point.push(x);
-        point.extend(vec![F::zero(); self.num_vars - round - 1]);
-
-        let mut sum = F::zero();
-        let remaining_vars = self.num_vars - round - 1;
-        let num_remaining_points = 1 << remaining_vars;
-
-        // Sum over all possible values of the remaining variables
-        for i in 0..num_remaining_points {
-            let mut full_point = point.clone();
-            for j in 0..remaining_vars {
-                if (i >> j) & 1 == 1 {
-                    full_point[round + 1 + j] = F::one();
-                }
-            }
-            sum += self.evaluate(&full_point);
-        }
-        sum
+        self.bind_prefix(&point).iter().sum()
     }
 
+    // Appends `blows` variables the polynomial doesn't depend on after the existing ones
     pub fn blow_up_right(&self, blows: u32) -> Self {
-        let mut new_coeffs = get_blow_up_poly(self, blows);
-        new_coeffs = new_coeffs
-            .iter()
-            .enumerate()
-            .map(|(i, _)| self.coeffs[i >> blows as usize])
-            .collect();
-        Self::new(new_coeffs, self.num_vars + blows as usize)
+        self.blow_up_at(blows, self.num_vars)
     }
 
+    // Prepends `blows` variables the polynomial doesn't depend on before the existing ones
     pub fn blow_up_left(&self, blows: u32) -> Self {
-        let mut new_coeffs = get_blow_up_poly(self, blows);
-        let mask = self.coeffs.len() - 1;
-        new_coeffs = new_coeffs
-            .iter()
-            .enumerate()
-            .map(|(i, _)| self.coeffs[i & mask])
+        self.blow_up_at(blows, 0)
+    }
+
+    // Inserts `blows` variables the polynomial doesn't depend on as variables `position..position + blows`,
+    // shifting the ones from `position` on to the right. Every entry is repeated once per value of the new bits.
+    pub fn blow_up_at(&self, blows: u32, position: usize) -> Self {
+        if position > self.num_vars {
+            panic!("Invalid variable index");
+        }
+        let blows = blows as usize;
+        // the variables after the insertion point keep the low bits of the index
+        let low_bits = self.num_vars - position;
+        let low_mask = (1 << low_bits) - 1;
+        let new_coeffs = (0..1usize << (self.num_vars + blows))
+            .map(|i| self.coeffs[((i >> (low_bits + blows)) << low_bits) | (i & low_mask)])
             .collect();
-        Self::new(new_coeffs, self.num_vars + blows as usize)
+        Self::new(new_coeffs, self.num_vars + blows)
     }
 
     pub fn scalar_mul(&self, value: F) -> Self {
         Self::new(self.coeffs.iter().map(|&x| x * value).collect(), self.num_vars)
     }
+
+    // `scalar_mul` without allocating a new coefficient vector
+    pub fn scalar_mul_assign(&mut self, value: F) {
+        self.coeffs.iter_mut().for_each(|x| *x *= value);
+    }
+
+    // Adds `value` to coeffs[0] only. The coefficients are evaluations, so this shifts f at the all-zeros
+    // point, i.e. adds value * eq(x, 0); every other entry is left as it is
+    pub fn add_scalar(&self, value: F) -> Self {
+        let mut coeffs = self.coeffs.clone();
+        coeffs[0] += value;
+        Self::new(coeffs, self.num_vars)
+    }
+
+    // Outer product on `self.num_vars + other.num_vars` variables, entry (j << self.num_vars) | i being
+    // self[i] * other[j]. `self` takes the low bits, which are the trailing variables, so the result evaluates at
+    // [other_point, self_point] to other(other_point) * self(self_point)
+    pub fn tensor(&self, other: &MultivariatePoly<F>) -> Self {
+        let coeffs = other
+            .coeffs
+            .iter()
+            .flat_map(|&b| self.coeffs.iter().map(move |&a| a * b))
+            .collect();
+        Self::new(coeffs, self.num_vars + other.num_vars)
+    }
+
+    // ∑ self.coeffs[i] * other.coeffs[i]; against `eq_poly(r)` this is the evaluation at `r`
+    pub fn dot(&self, other: &MultivariatePoly<F>) -> F {
+        if self.num_vars != other.num_vars {
+            panic!("Invalid number of variables");
+        }
+        self.coeffs.iter().zip(other.coeffs.iter()).map(|(&a, &b)| a * b).sum()
+    }
+}
+
+// eq(x, r) over the boolean hypercube, with the same bit-to-variable mapping as `evaluate`: each
+// variable is appended as the new least significant bit, leaving r[0] on the most significant one
+pub fn eq_poly<F: PrimeField>(r: &[F]) -> MultivariatePoly<F> {
+    let mut table = vec![F::one()];
+    for &r_j in r {
+        table = table
+            .iter()
+            .flat_map(|&value| [value * (F::one() - r_j), value * r_j])
+            .collect();
+    }
+    MultivariatePoly::new(table, r.len())
 }
 
 pub fn get_blow_up_poly<F: PrimeField>(poly: &MultivariatePoly<F>, blows: u32) -> Vec<F> {
@@ -212,6 +398,39 @@ impl<F: PrimeField> Add for MultivariatePoly<F> {
     }
 }
 
+// `+` without allocating: adds `other` into `self`'s coefficients
+impl<F: PrimeField> AddAssign<&MultivariatePoly<F>> for MultivariatePoly<F> {
+    fn add_assign(&mut self, other: &Self) {
+        if self.num_vars != other.num_vars {
+            panic!("Polynomials must have the same number of variables");
+        }
+        self.coeffs.iter_mut().zip(other.coeffs.iter()).for_each(|(a, b)| *a += b);
+    }
+}
+
+impl<F: PrimeField> Sub for MultivariatePoly<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        if self.num_vars != other.num_vars {
+            panic!("Polynomials must have the same number of variables");
+        }
+        let coeffs = self.coeffs.iter().zip(other.coeffs.iter())
+            .map(|(a, b)| *a - *b)
+            .collect();
+        Self::new(coeffs, self.num_vars)
+    }
+}
+
+impl<F: PrimeField> Neg for MultivariatePoly<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        let coeffs = self.coeffs.iter().map(|a| -*a).collect();
+        Self::new(coeffs, self.num_vars)
+    }
+}
+
 impl<F: PrimeField> Mul for MultivariatePoly<F> {
     type Output = Self;
 
@@ -227,21 +446,53 @@ impl<F: PrimeField> Mul for MultivariatePoly<F> {
     }
 }
 
+// num_vars as a u64, then the coefficients with their own length prefix
+impl<F: PrimeField> CanonicalSerialize for MultivariatePoly<F> {
+    fn serialize_with_mode<W: Write>(&self, mut writer: W, compress: Compress) -> Result<(), SerializationError> {
+        (self.num_vars as u64).serialize_with_mode(&mut writer, compress)?;
+        self.coeffs.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        (self.num_vars as u64).serialized_size(compress) + self.coeffs.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for MultivariatePoly<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.coeffs.check()
+    }
+}
+
+// Rejects an encoding whose coefficient count isn't 2^num_vars
+impl<F: PrimeField> CanonicalDeserialize for MultivariatePoly<F> {
+    fn deserialize_with_mode<R: Read>(mut reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        let num_vars = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coeffs = Vec::<F>::deserialize_with_mode(reader, compress, validate)?;
+        let num_vars = usize::try_from(num_vars).map_err(|_| SerializationError::InvalidData)?;
+        if num_vars >= usize::BITS as usize {
+            return Err(SerializationError::InvalidData);
+        }
+        Self::try_new(coeffs, num_vars).map_err(|_| SerializationError::InvalidData)
+    }
+}
+
 #[cfg(test)]
 /// Tests for the `MultivariatePoly` struct.
 ///
 /// # Tests
 ///
 /// - `test_new`: Tests the creation of a new `MultivariatePoly` instance with given coefficients and number of variables.
-/// - `test_evaluate`: Tests the evaluation of the polynomial at a given point. The polynomial evaluated at point (1, 1) should result in 10.
+/// - `test_evaluate`: Tests the evaluation of the polynomial at a given point. The polynomial evaluated at point (1, 1) should result in 4.
 /// - `test_sum_over_boolean_hypercube`: Tests the sum of the polynomial over the boolean hypercube. The sum should be 10.
-/// - `test_evaluate_at_round`: Tests the evaluation of the polynomial at a specific round with partial evaluation and a given value. The result should be 10.
+/// - `test_evaluate_at_round`: Tests the evaluation of the polynomial at a specific round with partial evaluation and a given value. The result should be 7.
 /// - `test_scalar_mul`: Tests the scalar multiplication of the polynomial. Each coefficient should be multiplied by the scalar value.
 /// - `test_blow_up_right`: Tests the increase of the number of variables by blowing up the polynomial to the right. The number of variables should increase by 1.
 /// - `test_blow_up_left`: Tests the increase of the number of variables by blowing up the polynomial to the left. The number of variables should increase by 1.
 
 mod tests {
     use super::*;
+    use ark_ff::UniformRand;
 
     #[test]
     /// Tests the creation of a new `MultivariatePoly` instance with given coefficients and number of variables.
@@ -252,16 +503,29 @@ mod tests {
         assert_eq!(poly.num_vars, 2);
     }
 
+    #[test]
+    /// Tests building the multilinear extension of the parity function on 2 variables.
+    fn test_from_boolean_fn_parity() {
+        let poly = MultivariatePoly::<Fr>::from_boolean_fn(2, |i| i.count_ones() % 2 == 1);
+        assert_eq!(poly.num_vars, 2);
+        assert_eq!(
+            poly.coeffs,
+            vec![Fr::from(0u64), Fr::from(1u64), Fr::from(1u64), Fr::from(0u64)]
+        );
+    }
+
     #[test]
     /// Tests the evaluation of the polynomial at a given point.
-    /// The polynomial evaluated at point (1, 1) should result in 10.
-    /// Equation: 1 + 2*1 + 3*1 + 4*1*1 = 10
+    /// Read MSB first, the evaluations 1, 2, 3, 4 give f(x, y) = 1 + 2x + y, so (1, 1) should result in 4.
     fn test_evaluate() {
+        // f(0,0) = 1, f(0,1) = 2, f(1,0) = 3, f(1,1) = 4, so f(x, y) = 1 + 2x + y
         let coeffs = vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)];
         let poly = MultivariatePoly::new(coeffs, 2);
         let point = vec![Fr::from(1u64), Fr::from(1u64)];
         let result = poly.evaluate(&point);
-        assert_eq!(result, Fr::from(10u64));
+        assert_eq!(result, Fr::from(4u64));
+        assert_eq!(poly.evaluate(&[Fr::from(1u64), Fr::from(0u64)]), Fr::from(3u64));
+        assert_eq!(poly.evaluate(&[Fr::from(5u64), Fr::from(7u64)]), Fr::from(18u64));
     }
 
     #[test]
@@ -275,27 +539,63 @@ mod tests {
 
     #[test]
     /// Tests the sum of the polynomial over the boolean hypercube.
-    /// The sum should be 18.
-    /// f(x, y) = 1 + 2x + 3y + 4xy
-    /// Equation: sum of evaluations at points (0,0), (0,1), (1,0), (1,1) = 1 + 3 + 2 + 12 = 18
+    /// The sum should be 10.
+    /// Equation: the evaluations at (0,0), (0,1), (1,0), (1,1) are the coefficients, 1 + 2 + 3 + 4 = 10
     fn test_sum_over_boolean_hypercube() {
         let coeffs = vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)];
         let poly = MultivariatePoly::new(coeffs, 2);
         let result = poly.sum_over_boolean_hypercube();
-        assert_eq!(result, Fr::from(18u64));
+        assert_eq!(result, Fr::from(10u64));
     }
 
     #[test]
     /// Tests the evaluation of the polynomial at a specific round with partial evaluation and a given value.
-    /// The result should be 10.
-    /// Equation: partial evaluation at round 0 with value 1 results in evaluation at point (1, 1) = 10
+    /// The result should be 7.
+    /// Equation: round 0 fixes the first variable to x = 1 and sums over the second, f(1, 0) + f(1, 1) = 3 + 4 = 7
     fn test_evaluate_at_round() {
         let coeffs = vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)];
         let poly = MultivariatePoly::new(coeffs, 2);
         let partial_evaluation = vec![Fr::from(1u64)];
         let x = Fr::from(1u64);
         let result = poly.evaluate_at_round(0, &partial_evaluation, x);
-        assert_eq!(result, Fr::from(10u64));
+        assert_eq!(result, Fr::from(7u64));
+    }
+
+    #[test]
+    /// Tests that the table-folding `evaluate_at_round` matches summing full `evaluate` calls over the
+    /// remaining hypercube, for every round of a 5-variable polynomial.
+    fn test_evaluate_at_round_matches_naive() {
+        fn naive_evaluate_at_round(poly: &MultivariatePoly<Fr>, round: usize, partial_evaluation: &[Fr], x: Fr) -> Fr {
+            let mut point = partial_evaluation[0..round].to_vec();
+            point.push(x);
+            point.extend(vec![Fr::from(0u64); poly.num_vars - round - 1]);
+
+            let remaining_vars = poly.num_vars - round - 1;
+            let mut sum = Fr::from(0u64);
+            for i in 0..(1 << remaining_vars) {
+                let mut full_point = point.clone();
+                for j in 0..remaining_vars {
+                    if (i >> j) & 1 == 1 {
+                        full_point[round + 1 + j] = Fr::from(1u64);
+                    }
+                }
+                sum += poly.evaluate(&full_point);
+            }
+            sum
+        }
+
+        let coeffs: Vec<Fr> = (0..32u64).map(|i| Fr::from(i * i + 3)).collect();
+        let poly = MultivariatePoly::new(coeffs, 5);
+        let partial_evaluation = vec![Fr::from(7u64), Fr::from(11u64), Fr::from(13u64), Fr::from(17u64)];
+
+        for round in 0..5 {
+            for x in [Fr::from(0u64), Fr::from(1u64), Fr::from(5u64)] {
+                assert_eq!(
+                    poly.evaluate_at_round(round, &partial_evaluation, x),
+                    naive_evaluate_at_round(&poly, round, &partial_evaluation, x)
+                );
+            }
+        }
     }
 
     #[test]
@@ -331,12 +631,36 @@ mod tests {
         assert_eq!(result.num_vars, 3);
     }
 
+    #[test]
+    fn test_blow_up_at() {
+        let mut rng = ark_std::test_rng();
+        let poly = MultivariatePoly::<Fr>::rand(3, &mut rng);
+        let point: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let expected = poly.evaluate(&point);
+
+        for position in 0..=3 {
+            let blown = poly.blow_up_at(2, position);
+            assert_eq!(blown.num_vars, 5);
+            for _ in 0..3 {
+                // any values for the two inserted variables
+                let fresh = [Fr::rand(&mut rng), Fr::rand(&mut rng)];
+                let new_point = [&point[..position], &fresh, &point[position..]].concat();
+                assert_eq!(blown.evaluate(&new_point), expected);
+            }
+        }
+
+        assert_eq!(poly.blow_up_left(2), poly.blow_up_at(2, 0));
+        assert_eq!(poly.blow_up_right(2), poly.blow_up_at(2, 3));
+        assert_eq!(poly.blow_up_at(0, 1), poly);
+    }
+
     #[test]
     /// Tests the partial evaluation of the polynomial f(x, y) = 4y + 7xy at x = 2.
     /// The result should be 18y.
     fn test_partial_evaluate_4y_7xy_at_x_2() {
         let coeffs = vec![Fr::from(0u64), Fr::from(4u64), Fr::from(0u64), Fr::from(11u64)];
         let poly = MultivariatePoly::new(coeffs, 2);
+        #[allow(deprecated)]
         let result = MultivariatePoly::partial_evaluate(&poly.coeffs, 0, Fr::from(2u64));
         let expected_result = vec![Fr::from(0u64), Fr::from(18u64)];
         assert_eq!(result, expected_result);
@@ -353,6 +677,375 @@ mod tests {
         assert_eq!(result, Fr::from(54u64));
     }
 
+    #[test]
+    fn test_serialize_round_trip() {
+        let poly = MultivariatePoly::<Fr>::rand(3, &mut ark_std::test_rng());
+        let mut bytes = vec![];
+        poly.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), poly.compressed_size());
+        assert_eq!(MultivariatePoly::<Fr>::deserialize_compressed(&bytes[..]).unwrap(), poly);
+
+        // claim 4 variables for the 8 coefficients
+        bytes[..8].copy_from_slice(&4u64.to_le_bytes());
+        assert!(matches!(
+            MultivariatePoly::<Fr>::deserialize_compressed(&bytes[..]),
+            Err(SerializationError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_boolean() {
+        let poly = MultivariatePoly::<Fr>::rand(3, &mut ark_std::test_rng());
+        for i in 0..8 {
+            let bits: Vec<bool> = (0..3).map(|j| (i >> (2 - j)) & 1 == 1).collect();
+            let point: Vec<Fr> = bits.iter().map(|&bit| Fr::from(bit as u64)).collect();
+            assert_eq!(poly.evaluate_boolean(&bits), poly.evaluate(&point));
+        }
+        assert_eq!(poly.evaluate_boolean(&[true, false, false]), poly.coeffs[0b100]);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let coeffs = vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)];
+        assert_eq!(MultivariatePoly::try_new(coeffs.clone(), 2), Err(PolyError::WrongLength { expected: 4, got: 3 }));
+        assert_eq!(MultivariatePoly::try_new(coeffs, 1), Err(PolyError::WrongLength { expected: 2, got: 3 }));
+        let poly = MultivariatePoly::try_new(vec![Fr::from(7u64); 4], 2).unwrap();
+        assert_eq!(poly, MultivariatePoly::new(vec![Fr::from(7u64); 4], 2));
+    }
+
+    #[test]
+    fn test_from_coeffs() {
+        for (len, num_vars) in [(1, 0), (2, 1), (8, 3)] {
+            let coeffs: Vec<Fr> = (0..len as u64).map(Fr::from).collect();
+            let poly = MultivariatePoly::from_coeffs(coeffs.clone());
+            assert_eq!(poly, MultivariatePoly::new(coeffs, num_vars));
+        }
+        assert_eq!(
+            MultivariatePoly::try_from_coeffs(vec![Fr::from(1u64); 6]),
+            Err(PolyError::NotPowerOfTwo { len: 6 })
+        );
+        assert_eq!(MultivariatePoly::<Fr>::try_from_coeffs(vec![]), Err(PolyError::NotPowerOfTwo { len: 0 }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Coefficient count 6 is not a power of two")]
+    fn test_from_coeffs_rejects_length_six() {
+        MultivariatePoly::from_coeffs(vec![Fr::from(1u64); 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid number of coefficients")]
+    fn test_new_rejects_wrong_length() {
+        MultivariatePoly::new(vec![Fr::from(1u64); 3], 2);
+    }
+
+    #[test]
+    fn test_try_partial_evaluate_out_of_range() {
+        let coeffs = vec![Fr::from(0u64), Fr::from(4u64), Fr::from(0u64), Fr::from(11u64)];
+        assert_eq!(
+            MultivariatePoly::try_partial_evaluate(&coeffs, 2, Fr::from(2u64)),
+            Err(PolyError::InvalidVariableIndex { index: 2, num_vars: 2 })
+        );
+        assert_eq!(
+            MultivariatePoly::try_partial_evaluate(&[Fr::from(29u64)], 0, Fr::from(2u64)),
+            Err(PolyError::InvalidVariableIndex { index: 0, num_vars: 0 })
+        );
+        assert_eq!(
+            MultivariatePoly::try_partial_evaluate(&coeffs, 0, Fr::from(2u64)),
+            Ok(vec![Fr::from(0u64), Fr::from(18u64)])
+        );
+    }
+
+    #[test]
+    fn test_partial_evaluate_ordered() {
+        // f(x_0, x_1, x_2) with f(i) = i, so f = 4x_0 + 2x_1 + x_2
+        let coeffs: Vec<Fr> = (0..8u64).map(Fr::from).collect();
+        let value = Fr::from(5u64);
+
+        // FromHigh 0 binds x_0: 20 + 2x_1 + x_2
+        let high = MultivariatePoly::partial_evaluate_ordered(&coeffs, 0, value, VarOrder::FromHigh);
+        assert_eq!(high, [20u64, 21, 22, 23].map(Fr::from).to_vec());
+        // FromLow 0 binds x_2: 4x_0 + 2x_1 + 5
+        let low = MultivariatePoly::partial_evaluate_ordered(&coeffs, 0, value, VarOrder::FromLow);
+        assert_eq!(low, [5u64, 7, 9, 11].map(Fr::from).to_vec());
+        assert_ne!(high, low);
+
+        // the two orders meet in the middle and swap at the ends
+        assert_eq!(
+            MultivariatePoly::partial_evaluate_ordered(&coeffs, 1, value, VarOrder::FromHigh),
+            MultivariatePoly::partial_evaluate_ordered(&coeffs, 1, value, VarOrder::FromLow)
+        );
+        assert_eq!(MultivariatePoly::partial_evaluate_ordered(&coeffs, 2, value, VarOrder::FromHigh), low);
+        assert_eq!(MultivariatePoly::partial_evaluate_ordered(&coeffs, 2, value, VarOrder::FromLow), high);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid variable index")]
+    fn test_partial_evaluate_ordered_out_of_range() {
+        let coeffs: Vec<Fr> = (0..8u64).map(Fr::from).collect();
+        MultivariatePoly::partial_evaluate_ordered(&coeffs, 3, Fr::from(1u64), VarOrder::FromLow);
+    }
+
+    #[test]
+    fn test_evaluate_options() {
+        let mut rng = ark_std::test_rng();
+        let poly = MultivariatePoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        let (a, b, c) = (Fr::from(2u64), Fr::from(5u64), Fr::from(7u64));
+
+        // all bound: the same scalar as binding the variables one after another
+        let full = poly.evaluate_options(&[Some(a), Some(b), Some(c)]);
+        assert_eq!(full.num_vars, 0);
+        assert_eq!(full.coeffs, vec![poly.evaluate_partial(&vec![a, b, c])]);
+        assert_eq!(full.coeffs, poly.solve(&vec![Some(a), Some(b), Some(c)]).coeffs);
+
+        // none bound: unchanged
+        assert_eq!(poly.evaluate_options(&[None, None, None]), poly);
+
+        // mixed: finishing the binding of the free variables gives the full evaluation
+        let middle_free = poly.evaluate_options(&[Some(a), None, Some(c)]);
+        assert_eq!(middle_free.num_vars, 1);
+        assert_eq!(middle_free.evaluate_options(&[Some(b)]), full);
+
+        let first_free = poly.evaluate_options(&[None, Some(b), Some(c)]);
+        assert_eq!(first_free.evaluate_options(&[Some(a)]), full);
+
+        let last_two_free = poly.evaluate_options(&[Some(a), None, None]);
+        assert_eq!(last_two_free.num_vars, 2);
+        assert_eq!(last_two_free.evaluate_options(&[Some(b), Some(c)]), full);
+        assert_eq!(last_two_free.evaluate_options(&[Some(b), None]), poly.evaluate_options(&[Some(a), Some(b), None]));
+    }
+
+    #[test]
+    fn test_fold_all() {
+        let mut rng = ark_std::test_rng();
+        let poly = MultivariatePoly::new((0..16).map(|_| Fr::rand(&mut rng)).collect(), 4);
+        let challenges: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+
+        let folded = poly.fold_all(&challenges);
+        assert_eq!(folded, poly.solve(&challenges.iter().map(|x| Some(*x)).collect()).coeffs[0]);
+        assert_eq!(folded, poly.evaluate_partial(&challenges));
+        assert_eq!(folded, poly.evaluate(&challenges));
+    }
+
+    #[test]
+    fn test_dot_with_eq_poly_is_evaluate() {
+        let mut rng = ark_std::test_rng();
+        let poly = MultivariatePoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        let r: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        assert_eq!(poly.dot(&eq_poly(&r)), poly.evaluate(&r));
+
+        // on a boolean point eq_poly is the indicator of that vertex
+        let vertex = eq_poly(&[Fr::from(1u64), Fr::from(0u64), Fr::from(1u64)]);
+        assert_eq!(poly.dot(&vertex), poly.coeffs[0b101]);
+    }
+
+    #[test]
+    fn test_pairs_differ_only_in_the_variable_bit() {
+        let num_vars = 3;
+        for index in 0..num_vars {
+            let bit = 1 << (num_vars - 1 - index);
+            let expected: Vec<(usize, usize)> = (0..1 << num_vars)
+                .filter(|i| i & bit == 0)
+                .map(|i| (i, i | bit))
+                .collect();
+            assert_eq!(MultivariatePoly::<Fr>::pairs(index, num_vars), expected);
+        }
+        assert_eq!(MultivariatePoly::<Fr>::pairs(1, 3), vec![(0, 2), (1, 3), (4, 6), (5, 7)]);
+    }
+
+    // ∑_i coeffs[i] * ∏_j (x_j or 1 - x_j by bit j of i), one term per hypercube point
+    fn evaluate_uncached(poly: &MultivariatePoly<Fr>, point: &[Fr]) -> Fr {
+        (0..poly.coeffs.len())
+            .map(|i| {
+                (0..poly.num_vars).fold(poly.coeffs[i], |term, j| {
+                    let bit = (i >> (poly.num_vars - 1 - j)) & 1;
+                    if bit == 1 { term * point[j] } else { term * (Fr::from(1u64) - point[j]) }
+                })
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_evaluate_matches_uncached() {
+        let mut rng = ark_std::test_rng();
+        let poly = MultivariatePoly::new((0..32).map(|_| Fr::rand(&mut rng)).collect(), 5);
+        for _ in 0..5 {
+            let point: Vec<Fr> = (0..5).map(|_| Fr::rand(&mut rng)).collect();
+            assert_eq!(poly.evaluate(&point), evaluate_uncached(&poly, &point));
+        }
+    }
+
+    #[test]
+    fn test_evaluate_matches_uncached_six_vars() {
+        let mut rng = ark_std::test_rng();
+        for _ in 0..5 {
+            let poly = MultivariatePoly::<Fr>::rand(6, &mut rng);
+            let point: Vec<Fr> = (0..6).map(|_| Fr::rand(&mut rng)).collect();
+            assert_eq!(poly.evaluate(&point), evaluate_uncached(&poly, &point));
+        }
+    }
+
+    // Benchmark only, agreement is checked by test_evaluate_matches_uncached_six_vars. Wall-clock numbers are
+    // too noisy to assert on, so it prints them: `cargo test --release -- --ignored --nocapture bench_evaluate`
+    #[test]
+    #[ignore]
+    fn bench_evaluate_against_uncached() {
+        let mut rng = ark_std::test_rng();
+        let poly = MultivariatePoly::<Fr>::rand(12, &mut rng);
+        let points: Vec<Vec<Fr>> = (0..5).map(|_| (0..12).map(|_| Fr::rand(&mut rng)).collect()).collect();
+
+        let start = std::time::Instant::now();
+        points.iter().for_each(|point| { std::hint::black_box(evaluate_uncached(&poly, point)); });
+        let uncached_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        points.iter().for_each(|point| { std::hint::black_box(poly.evaluate(point)); });
+        let folded_time = start.elapsed();
+
+        println!("12 variables, 5 points: per-term {uncached_time:?}, folding {folded_time:?}");
+    }
+
+    #[test]
+    fn test_sub() {
+        let mut rng = ark_std::test_rng();
+        let a = MultivariatePoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        let b = MultivariatePoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        assert_eq!((a.clone() + b.clone()) - b, a);
+        assert!((a.clone() - a).coeffs.iter().all(|x| *x == Fr::from(0u64)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Polynomials must have the same number of variables")]
+    fn test_sub_rejects_different_num_vars() {
+        let a = MultivariatePoly::new(vec![Fr::from(1u64); 4], 2);
+        let b = MultivariatePoly::new(vec![Fr::from(1u64); 2], 1);
+        let _ = a - b;
+    }
+
+    #[test]
+    fn test_neg() {
+        let mut rng = ark_std::test_rng();
+        let poly = MultivariatePoly::new((0..8).map(|_| Fr::rand(&mut rng)).collect(), 3);
+        let sum = poly.clone() + (-poly);
+        assert_eq!(sum.coeffs, vec![Fr::from(0u64); 8]);
+    }
+
+    #[test]
+    fn test_in_place_ops_match_operators() {
+        let mut rng = ark_std::test_rng();
+        let a = MultivariatePoly::<Fr>::rand(3, &mut rng);
+        let b = MultivariatePoly::<Fr>::rand(3, &mut rng);
+        let c = Fr::rand(&mut rng);
+
+        let mut scaled = a.clone();
+        scaled.scalar_mul_assign(c);
+        assert_eq!(scaled, a.scalar_mul(c));
+
+        let mut sum = a.clone();
+        sum += &b;
+        assert_eq!(sum, a + b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Polynomials must have the same number of variables")]
+    fn test_add_assign_rejects_mismatched_vars() {
+        let mut a = MultivariatePoly::new(vec![Fr::from(1u64); 4], 2);
+        a += &MultivariatePoly::new(vec![Fr::from(1u64); 8], 3);
+    }
+
+    #[test]
+    fn test_rewrap_matches_clone_then_new() {
+        let poly = MultivariatePoly::<Fr>::rand(3, &mut ark_std::test_rng());
+        let cloned = MultivariatePoly::new(poly.coeffs.clone(), 3);
+        assert_eq!(poly.as_coeffs(), &cloned.coeffs[..]);
+        assert_eq!(MultivariatePoly::rewrap(poly.into_coeffs()), Ok(cloned));
+        assert_eq!(MultivariatePoly::<Fr>::rewrap(vec![Fr::from(1u64); 6]), Err(PolyError::NotPowerOfTwo { len: 6 }));
+    }
+
+    #[test]
+    fn test_add_scalar() {
+        let coeffs = vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)];
+        let poly = MultivariatePoly::new(coeffs.clone(), 2);
+        let shifted = poly.add_scalar(Fr::from(5u64));
+        assert_eq!(shifted.coeffs[0], Fr::from(6u64));
+        assert_eq!(shifted.coeffs[1..], coeffs[1..]);
+        assert_eq!(shifted.num_vars, 2);
+    }
+
+    #[test]
+    fn test_rand() {
+        let poly = MultivariatePoly::<Fr>::rand(4, &mut ark_std::test_rng());
+        assert_eq!(poly.coeffs.len(), 16);
+        assert_eq!(poly.num_vars, 4);
+        // test_rng is seeded, so a fresh one reproduces the same polynomial
+        assert_eq!(MultivariatePoly::<Fr>::rand(4, &mut ark_std::test_rng()), poly);
+        assert_eq!(MultivariatePoly::<Fr>::rand(0, &mut ark_std::test_rng()).coeffs.len(), 1);
+    }
+
+    #[test]
+    fn test_tensor() {
+        let mut rng = ark_std::test_rng();
+        let a = MultivariatePoly::<Fr>::rand(2, &mut rng);
+        let b = MultivariatePoly::<Fr>::rand(3, &mut rng);
+        let tensor = a.tensor(&b);
+        assert_eq!(tensor.num_vars, 5);
+        assert_eq!(tensor.coeffs[(0b101 << 2) | 0b10], a.coeffs[0b10] * b.coeffs[0b101]);
+
+        for _ in 0..5 {
+            let a_point: Vec<Fr> = (0..2).map(|_| Fr::rand(&mut rng)).collect();
+            let b_point: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+            let point = [b_point.clone(), a_point.clone()].concat();
+            assert_eq!(tensor.evaluate(&point), a.evaluate(&a_point) * b.evaluate(&b_point));
+        }
+    }
+
+    #[test]
+    fn test_bit_order_convention() {
+        // a single one at index 0b110 is the indicator of the point (1, 1, 0)
+        let num_vars = 3;
+        let index = 0b110;
+        let poly = MultivariatePoly::from_boolean_fn(num_vars, |i| i == index);
+        let bits = |i: usize| -> Vec<Fr> {
+            (0..num_vars).map(|j| Fr::from(((i >> (num_vars - 1 - j)) & 1) as u64)).collect()
+        };
+
+        for i in 0..1 << num_vars {
+            let expected = if i == index { Fr::from(1u64) } else { Fr::from(0u64) };
+            assert_eq!(poly.evaluate(&bits(i)), expected);
+            assert_eq!(poly.fold_all(&bits(i)), expected);
+        }
+        assert_eq!(poly.evaluate(&[Fr::from(1u64), Fr::from(1u64), Fr::from(0u64)]), Fr::from(1u64));
+
+        // binding variable 0 to 1 keeps the entry, now at the index of the remaining bits (1, 0)
+        let bound = MultivariatePoly::partial_evaluate_ordered(&poly.coeffs, 0, Fr::from(1u64), VarOrder::FromHigh);
+        assert_eq!(bound, vec![Fr::from(0u64), Fr::from(0u64), Fr::from(1u64), Fr::from(0u64)]);
+        // and binding the last variable to 1 drops it
+        let bound = MultivariatePoly::partial_evaluate_ordered(&poly.coeffs, 2, Fr::from(1u64), VarOrder::FromHigh);
+        assert!(bound.iter().all(|x| *x == Fr::from(0u64)));
+
+        assert_eq!(poly.sum_over_boolean_hypercube(), Fr::from(1u64));
+        assert_eq!(poly.evaluate_at_round(0, &[], Fr::from(1u64)), Fr::from(1u64));
+        assert_eq!(poly.evaluate_at_round(0, &[], Fr::from(0u64)), Fr::from(0u64));
+    }
+
+    #[test]
+    fn test_with_num_vars() {
+        let poly = MultivariatePoly::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)], 2);
+        let grown = poly.with_num_vars(3).unwrap();
+        assert_eq!(grown.num_vars, 3);
+        assert_eq!(&grown.coeffs[..4], &poly.coeffs[..]);
+        assert!(grown.coeffs[4..].iter().all(|x| *x == Fr::from(0u64)));
+
+        // the new first variable selects between the original polynomial and zero
+        let (x, y) = (Fr::from(5u64), Fr::from(9u64));
+        assert_eq!(grown.fold_all(&[Fr::from(0u64), x, y]), poly.fold_all(&[x, y]));
+        assert_eq!(grown.fold_all(&[Fr::from(1u64), x, y]), Fr::from(0u64));
+
+        assert_eq!(grown.with_num_vars(2).unwrap(), poly);
+        assert_eq!(poly.with_num_vars(2).unwrap(), poly);
+        assert_eq!(poly.with_num_vars(1), Err(PolyError::LossyShrink { num_vars: 2, target: 1 }));
+    }
+
     #[test]
     fn test_solve() {
         let first = MultivariatePoly::new(
@@ -0,0 +1,88 @@
+use ark_ff::PrimeField;
+use crate::multilinear::MultivariatePoly;
+
+// A multilinear polynomial given by its non-zero evaluations over the boolean hypercube, as
+// `(index, value)` pairs using the same bit-to-variable mapping as `MultivariatePoly`. Suited to
+// tables such as GKR wiring predicates where almost every entry is zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMultilinear<F: PrimeField> {
+    pub entries: Vec<(usize, F)>,
+    pub num_vars: usize,
+}
+
+impl<F: PrimeField> SparseMultilinear<F> {
+    pub fn new(entries: Vec<(usize, F)>, num_vars: usize) -> Self {
+        if entries.iter().any(|&(index, _)| index >= 1 << num_vars) {
+            panic!("Index out of range");
+        }
+        Self { entries, num_vars }
+    }
+
+    // ∑ value · eq(index, point) over the stored entries
+    pub fn evaluate(&self, point: &[F]) -> F {
+        if point.len() != self.num_vars {
+            panic!("Invalid number of variables");
+        }
+        self.entries
+            .iter()
+            .map(|&(index, value)| {
+                (0..self.num_vars).fold(value, |term, j| {
+                    if (index >> (self.num_vars - 1 - j)) & 1 == 1 {
+                        term * point[j]
+                    } else {
+                        term * (F::one() - point[j])
+                    }
+                })
+            })
+            .sum()
+    }
+
+    pub fn to_dense(&self) -> MultivariatePoly<F> {
+        let mut coeffs = vec![F::zero(); 1 << self.num_vars];
+        for &(index, value) in self.entries.iter() {
+            coeffs[index] += value;
+        }
+        MultivariatePoly::new(coeffs, self.num_vars)
+    }
+}
+
+impl<F: PrimeField> From<&MultivariatePoly<F>> for SparseMultilinear<F> {
+    fn from(poly: &MultivariatePoly<F>) -> Self {
+        let entries = poly
+            .coeffs
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(index, &value)| (index, value))
+            .collect();
+        SparseMultilinear::new(entries, poly.num_vars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+
+    #[test]
+    fn test_sparse_matches_dense_wiring_poly() {
+        // three gates of a 9-variable wiring predicate
+        let sparse = SparseMultilinear::new(vec![(5, Fr::from(1u64)), (130, Fr::from(1u64)), (511, Fr::from(1u64))], 9);
+        let dense = sparse.to_dense();
+        assert_eq!(dense.coeffs.iter().filter(|x| **x != Fr::from(0u64)).count(), 3);
+        assert_eq!(SparseMultilinear::from(&dense), sparse);
+
+        let mut rng = ark_std::test_rng();
+        for _ in 0..5 {
+            let point: Vec<Fr> = (0..9).map(|_| Fr::rand(&mut rng)).collect();
+            assert_eq!(sparse.evaluate(&point), dense.evaluate(&point));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of range")]
+    fn test_rejects_out_of_range_index() {
+        SparseMultilinear::new(vec![(4, Fr::from(1u64))], 2);
+    }
+}
@@ -1,4 +1,26 @@
 use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate, Write};
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpolationError<F: PrimeField> {
+    DuplicateX { x: F },
+    // two points share `x` but disagree on the value there
+    InconsistentPoints { x: F },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+    // not of the form {"coefficients": [...]}
+    Malformed,
+    // a coefficient that isn't a quoted decimal field element
+    InvalidCoefficient(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DivError {
+    DivideByZero,
+}
 
 #[derive(Debug, Clone)]
 pub struct DensePolynomial<F: PrimeField> {
@@ -7,27 +29,284 @@ pub struct DensePolynomial<F: PrimeField> {
 
 impl<F: PrimeField> DensePolynomial<F> {
     pub fn new(coefficients: Vec<F>) -> Self {
+        debug_assert!(!coefficients.is_empty(), "DensePolynomial::new called with no coefficients, use [0] for zero");
+        Self::normalized(coefficients)
+    }
+
+    // Like `new` without the debug check, for internal results that may legitimately be empty, e.g. when
+    // an operand was built as a struct literal with no coefficients
+    fn normalized(coefficients: Vec<F>) -> Self {
         let mut coeffs = coefficients;
+        Self::trim(&mut coeffs);
+        DensePolynomial { coefficients: coeffs }
+    }
+
+    // For coefficients already known to be trimmed; only debug builds check that
+    pub fn new_trimmed(coefficients: Vec<F>) -> Self {
+        debug_assert!(
+            coefficients.len() <= 1 || coefficients.last().is_some_and(|x| !x.is_zero()),
+            "new_trimmed called with trailing zero coefficients"
+        );
+        DensePolynomial { coefficients }
+    }
+
+    // Builds the polynomial from `(coefficient, exponent)` terms; repeated exponents are summed
+    pub fn from_terms(terms: &[(F, usize)]) -> Self {
+        let len = terms.iter().map(|&(_, exp)| exp + 1).max().unwrap_or(1);
+        let mut coefficients = vec![F::zero(); len];
+        for &(coeff, exp) in terms {
+            coefficients[exp] += coeff;
+        }
+        Self::new(coefficients)
+    }
+
+    // Monic ∏ (X - r_i), e.g. the vanishing polynomial of a domain; [1] for no roots
+    pub fn from_roots(roots: &[F]) -> Self {
+        let mut poly = Self::new_trimmed(vec![F::one()]);
+        for &root in roots {
+            poly.mul_assign(&Self::new_trimmed(vec![-root, F::one()]));
+        }
+        poly
+    }
+
+    // {"coefficients": ["0", "3", "17"]} with every coefficient as a decimal string
+    pub fn to_json(&self) -> String {
+        let coefficients: Vec<String> = self.coefficients.iter().map(|c| format!("\"{}\"", c.into_bigint())).collect();
+        format!("{{\"coefficients\": [{}]}}", coefficients.join(", "))
+    }
+
+    // Parses `to_json` output; whitespace around tokens is ignored
+    pub fn from_json(s: &str) -> Result<Self, JsonError> {
+        let body = s.trim().strip_prefix('{').and_then(|s| s.strip_suffix('}')).ok_or(JsonError::Malformed)?;
+        let (key, value) = body.split_once(':').ok_or(JsonError::Malformed)?;
+        if key.trim() != "\"coefficients\"" {
+            return Err(JsonError::Malformed);
+        }
+        let list = value.trim().strip_prefix('[').and_then(|s| s.strip_suffix(']')).ok_or(JsonError::Malformed)?;
+        if list.trim().is_empty() {
+            return Ok(Self::new_trimmed(vec![F::zero()]));
+        }
+
+        let coefficients = list
+            .split(',')
+            .map(|item| {
+                let item = item.trim();
+                item.strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .and_then(|digits| F::from_str(digits).ok())
+                    .ok_or_else(|| JsonError::InvalidCoefficient(item.to_string()))
+            })
+            .collect::<Result<Vec<F>, JsonError>>()?;
+        Ok(Self::new(coefficients))
+    }
+
+    // Drops trailing zero coefficients, keeping at least one; an empty vector becomes [0]
+    fn trim(coeffs: &mut Vec<F>) {
         while coeffs.len() > 1 && coeffs.last().map_or(false, |&x| x.is_zero()) {
             coeffs.pop();
         }
-        DensePolynomial { coefficients: coeffs }
+        if coeffs.is_empty() {
+            coeffs.push(F::zero());
+        }
     }
 
     pub fn evaluate(&self, x: F) -> F {
-        self.coefficients
+        self.evaluate_horner(x)
+    }
+
+    // p(1) is the sum of the coefficients, with no multiplications
+    pub fn evaluate_at_one(&self) -> F {
+        self.coefficients.iter().sum()
+    }
+
+    // One multiply-add per coefficient, from the highest down
+    pub fn evaluate_horner(&self, x: F) -> F {
+        self.coefficients.iter().rev().fold(F::zero(), |acc, &coeff| acc * x + coeff)
+    }
+
+    // Estrin's scheme: pairs of coefficients are combined as c_2i + c_2i+1 * x, then pairs of those with x^2,
+    // and so on, so the products at each level are independent of each other
+    pub fn evaluate_estrin(&self, x: F) -> F {
+        let mut level = self.coefficients.clone();
+        let mut power = x;
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| if pair.len() == 2 { pair[0] + pair[1] * power } else { pair[0] })
+                .collect();
+            power.square_in_place();
+        }
+        level.first().copied().unwrap_or(F::zero())
+    }
+
+    // Indices of the points the polynomial doesn't pass through; empty when all of them lie on it
+    pub fn check_passes_through(&self, points: &[(F, F)]) -> Vec<usize> {
+        points
             .iter()
             .enumerate()
-            .map(|(i, &coef)| coef * x.pow([i as u64]))
-            .sum()
+            .filter(|&(_, &(x, y))| self.evaluate(x) != y)
+            .map(|(i, _)| i)
+            .collect()
     }
 
+    // Evaluates every polynomial at the same `x`, computing the powers of `x` once for all of them
+    pub fn evaluate_many_at(polys: &[DensePolynomial<F>], x: F) -> Vec<F> {
+        let max_len = polys.iter().map(|p| p.coefficients.len()).max().unwrap_or(0);
+        let mut powers = Vec::with_capacity(max_len);
+        let mut power = F::one();
+        for _ in 0..max_len {
+            powers.push(power);
+            power *= x;
+        }
+        polys
+            .iter()
+            .map(|p| p.coefficients.iter().zip(powers.iter()).map(|(&c, &xi)| c * xi).sum())
+            .collect()
+    }
+
+    // None for the zero polynomial, which `degree` can't tell apart from a non-zero constant
+    pub fn degree_opt(&self) -> Option<usize> {
+        self.coefficients.iter().rposition(|x| !x.is_zero())
+    }
+
+    // An empty coefficient vector is the zero polynomial, of degree 0 like any constant
     pub fn degree(&self) -> usize {
-        if self.coefficients.is_empty() {
-            0
-        } else {
-            self.coefficients.len() - 1
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn scalar_mul(&self, s: F) -> Self {
+        Self::normalized(self.coefficients.iter().map(|&c| c * s).collect())
+    }
+
+    pub fn neg(&self) -> Self {
+        self.scalar_mul(-F::one())
+    }
+
+    // Coefficient-wise `a + sign * b`, padding the shorter side with zeros
+    fn combine_coefficients(a: &[F], b: &[F], sign: F) -> Vec<F> {
+        let mut result = vec![F::zero(); a.len().max(b.len())];
+        for (i, &x) in a.iter().enumerate() {
+            result[i] += x;
+        }
+        for (i, &y) in b.iter().enumerate() {
+            result[i] += sign * y;
+        }
+        result
+    }
+
+    // [c0, c1, c2, ...] -> [c1, 2*c2, 3*c3, ...], and [0] for a constant
+    pub fn derivative(&self) -> Self {
+        if self.coefficients.len() <= 1 {
+            return Self::new_trimmed(vec![F::zero()]);
+        }
+        let coefficients = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| F::from(i as u64) * c)
+            .collect();
+        Self::new(coefficients)
+    }
+
+    // [c0, c1, c2, ...] -> [0, c0, c1/2, c2/3, ...], the antiderivative with zero constant term. Dividing
+    // by i + 1 needs the field characteristic to exceed degree + 1, otherwise this panics.
+    pub fn integrate(&self) -> Self {
+        let mut coefficients = vec![F::zero()];
+        for (i, &c) in self.coefficients.iter().enumerate() {
+            let inverse = F::from(i as u64 + 1)
+                .inverse()
+                .expect("integration needs the field characteristic to exceed the degree + 1");
+            coefficients.push(c * inverse);
+        }
+        Self::new(coefficients)
+    }
+
+    // Schoolbook product of two coefficient vectors
+    fn multiply_coefficients(a: &[F], b: &[F]) -> Vec<F> {
+        if a.is_empty() || b.is_empty() {
+            return vec![F::zero()];
+        }
+        let mut result = vec![F::zero(); a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                result[i + j] += x * y;
+            }
+        }
+        result
+    }
+
+    // Long division: (quotient, remainder) with self = quotient * divisor + remainder and the remainder
+    // of lower degree than the divisor
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), DivError> {
+        let divisor_degree = divisor.degree_opt().ok_or(DivError::DivideByZero)?;
+        let lead_inverse = divisor.coefficients[divisor_degree].inverse().unwrap();
+
+        let mut remainder = self.coefficients.clone();
+        Self::trim(&mut remainder);
+        if remainder.len() <= divisor_degree {
+            return Ok((Self::new_trimmed(vec![F::zero()]), Self::new_trimmed(remainder)));
+        }
+
+        let mut quotient = vec![F::zero(); remainder.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + divisor_degree] * lead_inverse;
+            quotient[i] = factor;
+            for (j, &d) in divisor.coefficients[..=divisor_degree].iter().enumerate() {
+                remainder[i + j] -= factor * d;
+            }
+        }
+        remainder.truncate(divisor_degree.max(1));
+        Ok((Self::new(quotient), Self::new(remainder)))
+    }
+
+    // Multiplies by `other` in place, growing the existing coefficient buffer instead of allocating a new one.
+    // Coefficients are written from the top down so every product only reads entries not yet overwritten.
+    pub fn mul_assign(&mut self, other: &DensePolynomial<F>) {
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            self.coefficients = vec![F::zero()];
+            return;
+        }
+
+        let self_len = self.coefficients.len();
+        let other_len = other.coefficients.len();
+        self.coefficients.resize(self_len + other_len - 1, F::zero());
+
+        for k in (0..self.coefficients.len()).rev() {
+            let start = k.saturating_sub(other_len - 1);
+            let end = k.min(self_len - 1);
+            let mut acc = F::zero();
+            for i in start..=end {
+                acc += self.coefficients[i] * other.coefficients[k - i];
+            }
+            self.coefficients[k] = acc;
         }
+        Self::trim(&mut self.coefficients);
+    }
+
+    // p(X)^exp by repeated in-place multiplication
+    pub fn pow(&self, exp: u64) -> Self {
+        let mut result = DensePolynomial::new_trimmed(vec![F::one()]);
+        for _ in 0..exp {
+            result.mul_assign(self);
+        }
+        result
+    }
+
+    // Composition p(q(X)), computed with Horner's rule over polynomials
+    pub fn compose(&self, other: &DensePolynomial<F>) -> Self {
+        let mut result = vec![F::zero()];
+        for &coef in self.coefficients.iter().rev() {
+            result = Self::multiply_coefficients(&result, &other.coefficients);
+            result[0] += coef;
+        }
+        DensePolynomial::new(result)
+    }
+
+    // Evaluates p at a polynomial point, returning p(point(X)); this is `compose` under the
+    // `evaluate` naming, so `p.evaluate_symbolic(&X) == p`
+    pub fn evaluate_symbolic(&self, point: &DensePolynomial<F>) -> Self {
+        self.compose(point)
     }
 
     // pub fn interpolate(points: &[(F, F)]) -> Self {
@@ -84,6 +363,12 @@ impl<F: PrimeField> DensePolynomial<F> {
     // Helper function to compute one term of basis polynomial (X - x_j)
     fn compute_linear_term(x_j: F, current: &[F]) -> Vec<F> {
         let n = current.len();
+        // X * current shifts every coefficient up by one, so the top slot must be free or it would be dropped
+        assert!(
+            current.last().is_none_or(|x| x.is_zero()),
+            "Lagrange basis buffer of size {} is too small to multiply by (X - x_j)",
+            n
+        );
         let mut new_term = vec![F::zero(); n];
         
         // Multiply polynomial by (X - x_j)
@@ -99,6 +384,7 @@ impl<F: PrimeField> DensePolynomial<F> {
     // Compute single Lagrange basis polynomial l_i(X)
     fn compute_lagrange_basis(i: usize, points: &[(F, F)]) -> Vec<F> {
         let n = points.len();
+        assert!(i < n, "Lagrange basis index {} out of range for {} points", i, n);
         let mut basis = vec![F::zero(); n];
         basis[0] = F::one();  // Start with 1
 
@@ -111,10 +397,29 @@ impl<F: PrimeField> DensePolynomial<F> {
         basis
     }
 
-    // Compute full Lagrange interpolation
+    // Compute full Lagrange interpolation; panics on a repeated x-coordinate, see `try_interpolate`
     pub fn interpolate(points: &[(F, F)]) -> Self {
+        Self::try_interpolate(points).expect("interpolation points must have distinct x-coordinates")
+    }
+
+    // Rejects a repeated x-coordinate before it reaches a zero denominator: `DuplicateX` when the
+    // points agree on y, `InconsistentPoints` when they don't
+    pub fn try_interpolate(points: &[(F, F)]) -> Result<Self, InterpolationError<F>> {
+        for (i, &(x_i, y_i)) in points.iter().enumerate() {
+            if let Some(&(_, y_j)) = points[..i].iter().find(|&&(x_j, _)| x_j == x_i) {
+                return Err(if y_j == y_i {
+                    InterpolationError::DuplicateX { x: x_i }
+                } else {
+                    InterpolationError::InconsistentPoints { x: x_i }
+                });
+            }
+        }
+        Ok(Self::lagrange_interpolate(points))
+    }
+
+    fn lagrange_interpolate(points: &[(F, F)]) -> Self {
         if points.is_empty() {
-            return DensePolynomial::new(vec![F::zero()]);
+            return DensePolynomial::new_trimmed(vec![F::zero()]);
         }
 
         let n = points.len();
@@ -141,6 +446,233 @@ impl<F: PrimeField> DensePolynomial<F> {
         DensePolynomial::new(result)
     }
 
+    // Barycentric form of the interpolating polynomial, cheaper to evaluate at many points than
+    // building the coefficients first
+    pub fn interpolate_barycentric(points: &[(F, F)]) -> BarycentricPoly<F> {
+        BarycentricPoly::new(points)
+    }
+
+    // The weights L_i(z) = ∏_{j != i} (z - x_j) / (x_i - x_j), so the polynomial through (x_i, y_i) has
+    // p(z) = Σ L_i(z) y_i. They depend only on the domain and z, so when many value vectors are opened over the
+    // same domain at the same point (Shamir shares at zero, or a fixed verifier challenge) they're computed once
+    // and each opening is a dot product with the y-values.
+    pub fn lagrange_coefficients_at(xs: &[F], z: F) -> Vec<F> {
+        if let Some(i) = xs.iter().position(|&x| x == z) {
+            let mut coefficients = vec![F::zero(); xs.len()];
+            coefficients[i] = F::one();
+            return coefficients;
+        }
+
+        // ∏_j (z - x_j), with the (z - x_i) factor divided back out per weight
+        let vanishing: F = xs.iter().map(|&x| z - x).product();
+        xs.iter()
+            .enumerate()
+            .map(|(i, &x_i)| {
+                let denominator: F = xs
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &x_j)| x_i - x_j)
+                    .product();
+                vanishing * (denominator * (z - x_i)).inverse().expect("interpolation points must have distinct x-coordinates")
+            })
+            .collect()
+    }
+
+    // Interpolation that rejects any repeated x-coordinate, even with a matching y, instead of
+    // failing on a zero denominator
+    pub fn checked_interpolate(points: &[(F, F)]) -> Result<Self, InterpolationError<F>> {
+        Self::try_interpolate(points).map_err(|err| match err {
+            InterpolationError::InconsistentPoints { x } => InterpolationError::DuplicateX { x },
+            err => err,
+        })
+    }
+
+}
+
+impl<F: PrimeField> Add for &DensePolynomial<F> {
+    type Output = DensePolynomial<F>;
+
+    fn add(self, other: Self) -> DensePolynomial<F> {
+        DensePolynomial::normalized(DensePolynomial::combine_coefficients(&self.coefficients, &other.coefficients, F::one()))
+    }
+}
+
+impl<F: PrimeField> Add for DensePolynomial<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        &self + &other
+    }
+}
+
+impl<F: PrimeField> Sub for &DensePolynomial<F> {
+    type Output = DensePolynomial<F>;
+
+    fn sub(self, other: Self) -> DensePolynomial<F> {
+        DensePolynomial::normalized(DensePolynomial::combine_coefficients(&self.coefficients, &other.coefficients, -F::one()))
+    }
+}
+
+impl<F: PrimeField> Sub for DensePolynomial<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        &self - &other
+    }
+}
+
+impl<F: PrimeField> Mul for &DensePolynomial<F> {
+    type Output = DensePolynomial<F>;
+
+    fn mul(self, other: Self) -> DensePolynomial<F> {
+        DensePolynomial::new(DensePolynomial::multiply_coefficients(&self.coefficients, &other.coefficients))
+    }
+}
+
+impl<F: PrimeField> Mul for DensePolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        &self * &other
+    }
+}
+
+// Coefficient count as a u64, then each coefficient's canonical encoding
+impl<F: PrimeField> CanonicalSerialize for DensePolynomial<F> {
+    fn serialize_with_mode<W: Write>(&self, writer: W, compress: Compress) -> Result<(), SerializationError> {
+        self.coefficients.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.coefficients.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for DensePolynomial<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.coefficients.check()
+    }
+}
+
+// Trims like `new`, so trailing zeros in the encoding don't survive the round trip and an empty one
+// decodes to [0]
+impl<F: PrimeField> CanonicalDeserialize for DensePolynomial<F> {
+    fn deserialize_with_mode<R: Read>(reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        let coefficients = Vec::<F>::deserialize_with_mode(reader, compress, validate)?;
+        Ok(DensePolynomial::normalized(coefficients))
+    }
+}
+
+// Interpolating polynomial kept as its nodes, values and weights w_i = 1 / ∏_{j != i} (x_i - x_j)
+#[derive(Debug, Clone)]
+pub struct BarycentricPoly<F: PrimeField> {
+    pub xs: Vec<F>,
+    pub ys: Vec<F>,
+    pub weights: Vec<F>,
+}
+
+impl<F: PrimeField> BarycentricPoly<F> {
+    pub fn new(points: &[(F, F)]) -> Self {
+        let weights = (0..points.len())
+            .map(|i| DensePolynomial::compute_lagrange_denominator(points[i].0, points, i).inverse().unwrap())
+            .collect();
+        BarycentricPoly {
+            xs: points.iter().map(|&(x, _)| x).collect(),
+            ys: points.iter().map(|&(_, y)| y).collect(),
+            weights,
+        }
+    }
+
+    // p(x) = (Σ w_i y_i / (x - x_i)) / (Σ w_i / (x - x_i)), or y_i directly when x is a node
+    pub fn evaluate(&self, x: F) -> F {
+        if self.xs.is_empty() {
+            return F::zero();
+        }
+        if let Some(i) = self.xs.iter().position(|&x_i| x_i == x) {
+            return self.ys[i];
+        }
+
+        let mut numerator = F::zero();
+        let mut denominator = F::zero();
+        for ((&x_i, &y_i), &w_i) in self.xs.iter().zip(self.ys.iter()).zip(self.weights.iter()) {
+            let term = w_i * (x - x_i).inverse().unwrap();
+            numerator += term * y_i;
+            denominator += term;
+        }
+        numerator * denominator.inverse().unwrap()
+    }
+}
+
+// X^min_degree * inner(X), so negative powers of X are allowed. Kept normalized with a non-zero lowest
+// coefficient (or as the zero polynomial with min_degree 0), which makes equal polynomials structurally equal.
+#[derive(Debug, Clone)]
+pub struct LaurentPolynomial<F: PrimeField> {
+    pub inner: DensePolynomial<F>,
+    pub min_degree: i64,
+}
+
+impl<F: PrimeField> LaurentPolynomial<F> {
+    pub fn new(inner: DensePolynomial<F>, min_degree: i64) -> Self {
+        let mut coefficients = inner.coefficients;
+        let mut min_degree = min_degree;
+        match coefficients.iter().position(|x| !x.is_zero()) {
+            Some(shift) => {
+                coefficients.drain(..shift);
+                min_degree += shift as i64;
+            }
+            None => {
+                coefficients = vec![F::zero()];
+                min_degree = 0;
+            }
+        }
+        LaurentPolynomial { inner: DensePolynomial::new(coefficients), min_degree }
+    }
+
+    // Panics if `x` is zero and the polynomial has negative powers
+    pub fn evaluate(&self, x: F) -> F {
+        let shift = if self.min_degree < 0 {
+            x.inverse().expect("Laurent polynomial with negative powers evaluated at zero").pow([self.min_degree.unsigned_abs()])
+        } else {
+            x.pow([self.min_degree as u64])
+        };
+        shift * self.inner.evaluate(x)
+    }
+
+    // Coefficients re-based to start at `min_degree`, which must not exceed `self.min_degree`
+    fn coefficients_from(&self, min_degree: i64) -> Vec<F> {
+        let mut coefficients = vec![F::zero(); (self.min_degree - min_degree) as usize];
+        coefficients.extend(&self.inner.coefficients);
+        coefficients
+    }
+}
+
+impl<F: PrimeField> Add for LaurentPolynomial<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let min_degree = self.min_degree.min(other.min_degree);
+        let mut coefficients = self.coefficients_from(min_degree);
+        let other_coefficients = other.coefficients_from(min_degree);
+        if other_coefficients.len() > coefficients.len() {
+            coefficients.resize(other_coefficients.len(), F::zero());
+        }
+        for (i, coeff) in other_coefficients.into_iter().enumerate() {
+            coefficients[i] += coeff;
+        }
+        LaurentPolynomial::new(DensePolynomial::new(coefficients), min_degree)
+    }
+}
+
+impl<F: PrimeField> Mul for LaurentPolynomial<F> {
+    type Output = Self;
+
+    // multiplying by X^a X^b adds the exponents
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Self) -> Self {
+        let coefficients = DensePolynomial::multiply_coefficients(&self.inner.coefficients, &other.inner.coefficients);
+        LaurentPolynomial::new(DensePolynomial::new(coefficients), self.min_degree + other.min_degree)
+    }
 }
 
 #[cfg(test)]
@@ -166,6 +698,32 @@ impl<F: PrimeField> DensePolynomial<F> {
 mod tests {
     use super::*;
     use ark_bn254::Fr;
+    use ark_ff::{Field, UniformRand};
+
+    // Checks `poly` against every sample point, and at random field points checks its Horner
+    // evaluation against the Lagrange form of `points` evaluated directly
+    fn assert_interpolates<F: PrimeField>(poly: &DensePolynomial<F>, points: &[(F, F)]) {
+        let horner = |x: F| poly.coefficients.iter().rev().fold(F::zero(), |acc, &c| acc * x + c);
+        let lagrange = |x: F| -> F {
+            points.iter().enumerate().map(|(i, &(x_i, y_i))| {
+                let (num, den) = points.iter().enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .fold((F::one(), F::one()), |(num, den), (_, &(x_j, _))| (num * (x - x_j), den * (x_i - x_j)));
+                y_i * num * den.inverse().unwrap()
+            }).sum()
+        };
+
+        for &(x, y) in points {
+            assert_eq!(poly.evaluate(x), y);
+        }
+
+        let mut rng = ark_std::test_rng();
+        for _ in 0..5 {
+            let x = F::rand(&mut rng);
+            assert_eq!(horner(x), lagrange(x));
+            assert_eq!(poly.evaluate(x), horner(x));
+        }
+    }
 
     #[test]
     fn test_linear_interpolation() {
@@ -173,8 +731,7 @@ mod tests {
         let poly = DensePolynomial::interpolate(&points);
         
         assert_eq!(poly.evaluate(Fr::from(0u64)), Fr::from(0u64));
-        assert_eq!(poly.evaluate(Fr::from(1u64)), Fr::from(2u64));
-        assert_eq!(poly.evaluate(Fr::from(2u64)), Fr::from(4u64));
+        assert_interpolates(&poly, &points);
     }
 
     #[test]
@@ -186,9 +743,7 @@ mod tests {
         ];
         let poly = DensePolynomial::interpolate(&points);
         
-        assert_eq!(poly.evaluate(Fr::from(0u64)), Fr::from(0u64));
-        assert_eq!(poly.evaluate(Fr::from(1u64)), Fr::from(1u64));
-        assert_eq!(poly.evaluate(Fr::from(2u64)), Fr::from(4u64));
+        assert_interpolates(&poly, &points);
     }
 
     #[test]
@@ -201,10 +756,17 @@ mod tests {
         ];
         let poly = DensePolynomial::interpolate(&points);
         
-        assert_eq!(poly.evaluate(Fr::from(0u64)), Fr::from(0u64));
-        assert_eq!(poly.evaluate(Fr::from(1u64)), Fr::from(1u64));
-        assert_eq!(poly.evaluate(Fr::from(2u64)), Fr::from(8u64));
-        assert_eq!(poly.evaluate(Fr::from(3u64)), Fr::from(27u64));
+        assert_interpolates(&poly, &points);
+    }
+
+    #[test]
+    fn test_random_interpolation() {
+        let mut rng = ark_std::test_rng();
+        let points: Vec<(Fr, Fr)> = (0..10).map(|_| (Fr::rand(&mut rng), Fr::rand(&mut rng))).collect();
+        let poly = DensePolynomial::interpolate(&points);
+
+        assert_eq!(poly.degree(), 9);
+        assert_interpolates(&poly, &points);
     }
 
     #[test]
@@ -219,7 +781,546 @@ mod tests {
         let poly = DensePolynomial::interpolate(&points);
         
         assert_eq!(poly.evaluate(Fr::from(0u64)), Fr::from(5u64));
-        assert_eq!(poly.evaluate(Fr::from(1u64)), Fr::from(5u64));
-        assert_eq!(poly.evaluate(Fr::from(2u64)), Fr::from(5u64));
+        assert_interpolates(&poly, &points);
+    }
+
+    #[test]
+    fn test_interpolation_with_twelve_points() {
+        // p(x) = 1 + 2x + 3x^2 + ... + 12x^11
+        let coefficients: Vec<Fr> = (1..=12u64).map(Fr::from).collect();
+        let expected = DensePolynomial::new(coefficients);
+        let points: Vec<(Fr, Fr)> = (0..12u64)
+            .map(|x| (Fr::from(x), expected.evaluate(Fr::from(x))))
+            .collect();
+
+        let poly = DensePolynomial::interpolate(&points);
+        assert_eq!(poly.coefficients, expected.coefficients);
+        assert_eq!(poly.evaluate(Fr::from(100u64)), expected.evaluate(Fr::from(100u64)));
+    }
+
+    #[test]
+    #[should_panic(expected = "too small")]
+    fn test_linear_term_panics_on_full_buffer() {
+        // x^2 stored in a 3-slot buffer has no room for another factor
+        let current = vec![Fr::from(0u64), Fr::from(0u64), Fr::from(1u64)];
+        DensePolynomial::compute_linear_term(Fr::from(1u64), &current);
+    }
+
+    #[test]
+    fn test_evaluate_symbolic() {
+        // X^2 at X + 1 is X^2 + 2X + 1
+        let square = DensePolynomial::new(vec![Fr::from(0u64), Fr::from(0u64), Fr::from(1u64)]);
+        let x_plus_one = DensePolynomial::new(vec![Fr::from(1u64), Fr::from(1u64)]);
+        let shifted = square.evaluate_symbolic(&x_plus_one);
+        assert_eq!(shifted.coefficients, vec![Fr::from(1u64), Fr::from(2u64), Fr::from(1u64)]);
+
+        for x in [Fr::from(0u64), Fr::from(3u64), Fr::from(10u64)] {
+            assert_eq!(shifted.evaluate(x), square.evaluate(x + Fr::from(1u64)));
+        }
+
+        // evaluating at X is the identity
+        let p = DensePolynomial::new(vec![Fr::from(5u64), Fr::from(0u64), Fr::from(7u64), Fr::from(2u64)]);
+        let x = DensePolynomial::new(vec![Fr::from(0u64), Fr::from(1u64)]);
+        assert_eq!(p.evaluate_symbolic(&x).coefficients, p.coefficients);
+        assert_eq!(p.evaluate_symbolic(&x).coefficients, p.compose(&x).coefficients);
+    }
+
+    #[test]
+    fn test_mul_assign_matches_schoolbook_product() {
+        // (X - 1)(X - 2)(X - 3)(X - 4)(X - 5)
+        let factors: Vec<DensePolynomial<Fr>> = (1..=5u64)
+            .map(|r| DensePolynomial::new(vec![-Fr::from(r), Fr::from(1u64)]))
+            .collect();
+
+        let mut in_place = DensePolynomial::new(vec![Fr::from(1u64)]);
+        for factor in &factors {
+            in_place.mul_assign(factor);
+        }
+
+        let folded = factors.iter().fold(vec![Fr::from(1u64)], |acc, factor| {
+            DensePolynomial::multiply_coefficients(&acc, &factor.coefficients)
+        });
+
+        assert_eq!(in_place.coefficients, folded);
+        assert_eq!(in_place.degree(), 5);
+        for r in 1..=5u64 {
+            assert_eq!(in_place.evaluate(Fr::from(r)), Fr::from(0u64));
+        }
+    }
+
+    #[test]
+    fn test_mul_assign_by_zero_and_pow() {
+        let mut p = DensePolynomial::new(vec![Fr::from(3u64), Fr::from(2u64)]);
+        p.mul_assign(&DensePolynomial::new(vec![Fr::from(0u64)]));
+        assert_eq!(p.coefficients, vec![Fr::from(0u64)]);
+
+        // (X + 1)^3 = X^3 + 3X^2 + 3X + 1
+        let x_plus_one = DensePolynomial::new(vec![Fr::from(1u64), Fr::from(1u64)]);
+        assert_eq!(
+            x_plus_one.pow(3).coefficients,
+            vec![Fr::from(1u64), Fr::from(3u64), Fr::from(3u64), Fr::from(1u64)]
+        );
+        assert_eq!(x_plus_one.pow(0).coefficients, vec![Fr::from(1u64)]);
+    }
+
+    #[test]
+    fn test_new_trimmed_matches_new() {
+        let inputs = [
+            vec![Fr::from(0u64)],
+            vec![Fr::from(4u64)],
+            vec![Fr::from(1u64), Fr::from(0u64), Fr::from(9u64)],
+        ];
+        for coefficients in inputs {
+            assert_eq!(
+                DensePolynomial::new_trimmed(coefficients.clone()).coefficients,
+                DensePolynomial::new(coefficients).coefficients
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "trailing zero")]
+    fn test_new_trimmed_rejects_untrimmed() {
+        DensePolynomial::new_trimmed(vec![Fr::from(1u64), Fr::from(0u64)]);
+    }
+
+    #[test]
+    fn test_empty_coefficients_are_zero() {
+        // as built by a struct literal, bypassing `new`
+        let empty = DensePolynomial::<Fr> { coefficients: vec![] };
+        let zero = vec![Fr::from(0u64)];
+        let p = DensePolynomial::new(vec![Fr::from(3u64), Fr::from(2u64)]);
+
+        assert_eq!(empty.evaluate(Fr::from(7u64)), Fr::from(0u64));
+        assert_eq!(empty.evaluate_estrin(Fr::from(7u64)), Fr::from(0u64));
+        assert_eq!(empty.degree(), 0);
+        assert_eq!(empty.degree_opt(), None);
+
+        assert_eq!((&empty + &empty).coefficients, zero);
+        assert_eq!((&empty - &empty).coefficients, zero);
+        assert_eq!((&empty + &p).coefficients, p.coefficients);
+        assert_eq!((&p - &empty).coefficients, p.coefficients);
+        assert_eq!((&empty * &p).coefficients, zero);
+        assert_eq!(empty.scalar_mul(Fr::from(5u64)).coefficients, zero);
+        assert_eq!(empty.neg().coefficients, zero);
+        assert_eq!(empty.derivative().coefficients, zero);
+        let (quotient, remainder) = empty.div_rem(&p).unwrap();
+        assert_eq!((quotient.coefficients, remainder.coefficients), (zero.clone(), zero.clone()));
+        assert_eq!(p.compose(&empty).coefficients, vec![Fr::from(3u64)]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "no coefficients")]
+    fn test_new_rejects_empty() {
+        DensePolynomial::<Fr>::new(vec![]);
+    }
+
+    #[test]
+    fn test_checked_interpolate_rejects_duplicate_x() {
+        let consistent = [
+            (Fr::from(1u64), Fr::from(2u64)),
+            (Fr::from(3u64), Fr::from(4u64)),
+            (Fr::from(1u64), Fr::from(2u64)),
+        ];
+        let conflicting = [
+            (Fr::from(1u64), Fr::from(2u64)),
+            (Fr::from(3u64), Fr::from(4u64)),
+            (Fr::from(3u64), Fr::from(5u64)),
+        ];
+        assert_eq!(
+            DensePolynomial::checked_interpolate(&consistent).unwrap_err(),
+            InterpolationError::DuplicateX { x: Fr::from(1u64) }
+        );
+        assert_eq!(
+            DensePolynomial::checked_interpolate(&conflicting).unwrap_err(),
+            InterpolationError::DuplicateX { x: Fr::from(3u64) }
+        );
+
+        let distinct = [(Fr::from(1u64), Fr::from(2u64)), (Fr::from(3u64), Fr::from(4u64))];
+        let poly = DensePolynomial::checked_interpolate(&distinct).unwrap();
+        assert_eq!(poly.coefficients, DensePolynomial::interpolate(&distinct).coefficients);
+    }
+
+    #[test]
+    fn test_try_interpolate_tells_duplicate_from_inconsistent() {
+        let duplicate = [(Fr::from(1u64), Fr::from(2u64)), (Fr::from(1u64), Fr::from(2u64))];
+        let inconsistent = [(Fr::from(1u64), Fr::from(2u64)), (Fr::from(1u64), Fr::from(5u64))];
+        assert_eq!(
+            DensePolynomial::try_interpolate(&duplicate).unwrap_err(),
+            InterpolationError::DuplicateX { x: Fr::from(1u64) }
+        );
+        assert_eq!(
+            DensePolynomial::try_interpolate(&inconsistent).unwrap_err(),
+            InterpolationError::InconsistentPoints { x: Fr::from(1u64) }
+        );
+
+        let points = [(Fr::from(0u64), Fr::from(1u64)), (Fr::from(2u64), Fr::from(5u64))];
+        let poly = DensePolynomial::try_interpolate(&points).unwrap();
+        assert_eq!(poly.coefficients, vec![Fr::from(1u64), Fr::from(2u64)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "interpolation points must have distinct x-coordinates")]
+    fn test_interpolate_panics_on_duplicate_x() {
+        DensePolynomial::interpolate(&[(Fr::from(1u64), Fr::from(2u64)), (Fr::from(1u64), Fr::from(3u64))]);
+    }
+
+    #[test]
+    fn test_lagrange_coefficients_at() {
+        let mut rng = ark_std::test_rng();
+        let xs: Vec<Fr> = (0..6).map(|_| Fr::rand(&mut rng)).collect();
+        let z = Fr::rand(&mut rng);
+        let weights = DensePolynomial::lagrange_coefficients_at(&xs, z);
+
+        // one set of weights opens every value vector over the same domain
+        for _ in 0..3 {
+            let ys: Vec<Fr> = (0..6).map(|_| Fr::rand(&mut rng)).collect();
+            let points: Vec<(Fr, Fr)> = xs.iter().copied().zip(ys.iter().copied()).collect();
+            let opened: Fr = weights.iter().zip(ys.iter()).map(|(&w, &y)| w * y).sum();
+            assert_eq!(opened, DensePolynomial::interpolate(&points).evaluate(z));
+        }
+
+        // at a node the weights pick out that node's value
+        let at_node = DensePolynomial::lagrange_coefficients_at(&xs, xs[2]);
+        assert_eq!(at_node, (0..6).map(|i| Fr::from((i == 2) as u64)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_barycentric_matches_interpolate() {
+        let mut rng = ark_std::test_rng();
+        let points: Vec<(Fr, Fr)> = (0..8).map(|_| (Fr::rand(&mut rng), Fr::rand(&mut rng))).collect();
+        let poly = DensePolynomial::interpolate(&points);
+        let barycentric = BarycentricPoly::new(&points);
+
+        for &(x, y) in points.iter() {
+            assert_eq!(barycentric.evaluate(x), y);
+        }
+        for _ in 0..5 {
+            let x = Fr::rand(&mut rng);
+            assert_eq!(barycentric.evaluate(x), poly.evaluate(x));
+        }
+        assert_eq!(barycentric.evaluate(Fr::from(0u64)), poly.evaluate(Fr::from(0u64)));
+        assert_eq!(DensePolynomial::interpolate_barycentric(&points).weights, barycentric.weights);
+    }
+
+    #[test]
+    fn test_evaluate_at_one_matches_evaluate() {
+        let mut rng = ark_std::test_rng();
+        for len in [1, 2, 3, 8, 33] {
+            let poly = DensePolynomial::new((0..len).map(|_| Fr::rand(&mut rng)).collect());
+            assert_eq!(poly.evaluate_at_one(), poly.evaluate(Fr::from(1u64)));
+        }
+        let poly = DensePolynomial::new(vec![Fr::from(3u64), Fr::from(0u64), Fr::from(5u64)]);
+        assert_eq!(poly.evaluate_at_one(), Fr::from(8u64));
+    }
+
+    #[test]
+    fn test_evaluate_estrin_matches_evaluate() {
+        let mut rng = ark_std::test_rng();
+        let poly = DensePolynomial::new((0..32).map(|_| Fr::rand(&mut rng)).collect());
+        assert_eq!(poly.degree(), 31);
+        for x in [Fr::from(0u64), Fr::from(1u64), Fr::rand(&mut rng), Fr::rand(&mut rng)] {
+            assert_eq!(poly.evaluate_estrin(x), poly.evaluate(x));
+        }
+
+        // odd number of coefficients leaves an unpaired one at some level
+        let odd = DensePolynomial::new((0..7).map(|_| Fr::rand(&mut rng)).collect());
+        let x = Fr::rand(&mut rng);
+        assert_eq!(odd.evaluate_estrin(x), odd.evaluate(x));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_laurent_polynomial() {
+        let x_inv = LaurentPolynomial::new(DensePolynomial::new(vec![Fr::from(1u64)]), -1);
+        let x = LaurentPolynomial::new(DensePolynomial::new(vec![Fr::from(0u64), Fr::from(1u64)]), 0);
+        let one = x_inv.clone() * x.clone();
+        assert_eq!(one.min_degree, 0);
+        assert_eq!(one.inner.coefficients, vec![Fr::from(1u64)]);
+
+        // 3X^-2 + 5 + 2X^2
+        let p = LaurentPolynomial::new(
+            DensePolynomial::new(vec![Fr::from(3u64), Fr::from(0u64), Fr::from(5u64), Fr::from(0u64), Fr::from(2u64)]),
+            -2,
+        );
+        let point = Fr::from(7u64);
+        let expected = Fr::from(3u64) * point.inverse().unwrap().square() + Fr::from(5u64) + Fr::from(2u64) * point.square();
+        assert_eq!(p.evaluate(point), expected);
+
+        // (p + X^-1)(y) = p(y) + 1/y and (p * X)(y) = p(y) * y
+        assert_eq!((p.clone() + x_inv.clone()).evaluate(point), expected + point.inverse().unwrap());
+        assert_eq!((p.clone() * x).evaluate(point), expected * point);
+
+        // the lowest term cancels, so the sum starts at X^0
+        let minus_three_x_inv_sq = LaurentPolynomial::new(DensePolynomial::new(vec![-Fr::from(3u64)]), -2);
+        let sum = p + minus_three_x_inv_sq;
+        assert_eq!(sum.min_degree, 0);
+        assert_eq!(sum.inner.coefficients, vec![Fr::from(5u64), Fr::from(0u64), Fr::from(2u64)]);
+    }
+
+    #[test]
+    fn test_evaluate_many_at_matches_evaluate() {
+        let mut rng = ark_std::test_rng();
+        let polys: Vec<DensePolynomial<Fr>> = [1, 4, 7]
+            .iter()
+            .map(|&len| DensePolynomial::new((0..len).map(|_| Fr::rand(&mut rng)).collect()))
+            .collect();
+        let x = Fr::rand(&mut rng);
+
+        let expected: Vec<Fr> = polys.iter().map(|p| p.evaluate(x)).collect();
+        assert_eq!(DensePolynomial::evaluate_many_at(&polys, x), expected);
+        assert!(DensePolynomial::<Fr>::evaluate_many_at(&[], x).is_empty());
+    }
+
+    #[test]
+    fn test_add_and_sub() {
+        // (1 + 2x + 3x^2) + (4 + 5x) = 5 + 7x + 3x^2
+        let p = DensePolynomial::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)]);
+        let q = DensePolynomial::new(vec![Fr::from(4u64), Fr::from(5u64)]);
+        assert_eq!((&p + &q).coefficients, vec![Fr::from(5u64), Fr::from(7u64), Fr::from(3u64)]);
+        assert_eq!((&q + &p).coefficients, (p.clone() + q.clone()).coefficients);
+        assert_eq!((&(&p - &q) + &q).coefficients, p.coefficients);
+
+        // leading terms cancelling drops the degree
+        let r = DensePolynomial::new(vec![Fr::from(0u64), Fr::from(1u64), Fr::from(3u64)]);
+        assert_eq!((&p - &r).coefficients, vec![Fr::from(1u64), Fr::from(1u64)]);
+        assert_eq!((&p - &r).degree(), 1);
+    }
+
+    #[test]
+    fn test_add_and_sub_cancel_to_zero() {
+        let mut rng = ark_std::test_rng();
+        let p = DensePolynomial::new((0..5).map(|_| Fr::rand(&mut rng)).collect());
+        let negated = DensePolynomial::new(p.coefficients.iter().map(|&x| -x).collect());
+
+        let sum = p.clone() + negated;
+        assert_eq!(sum.coefficients, vec![Fr::from(0u64)]);
+        assert_eq!(sum.degree_opt(), None);
+
+        let difference = p.clone() - p;
+        assert_eq!(difference.coefficients, vec![Fr::from(0u64)]);
+        assert_eq!(difference.degree(), 0);
+    }
+
+    #[test]
+    fn test_mul() {
+        // (x + 1)(x - 1) = x^2 - 1
+        let a = DensePolynomial::new(vec![Fr::from(1u64), Fr::from(1u64)]);
+        let b = DensePolynomial::new(vec![-Fr::from(1u64), Fr::from(1u64)]);
+        assert_eq!((&a * &b).coefficients, vec![-Fr::from(1u64), Fr::from(0u64), Fr::from(1u64)]);
+        assert_eq!((a.clone() * b).degree(), 2);
+
+        let zero = DensePolynomial::new(vec![Fr::from(0u64)]);
+        assert_eq!((&a * &zero).coefficients, vec![Fr::from(0u64)]);
+        let one = DensePolynomial::new(vec![Fr::from(1u64)]);
+        assert_eq!((&a * &one).coefficients, a.coefficients);
+    }
+
+    #[test]
+    fn test_from_terms() {
+        let poly = DensePolynomial::from_terms(&[(Fr::from(1u64), 0), (Fr::from(3u64), 2)]);
+        assert_eq!(poly.coefficients, vec![Fr::from(1u64), Fr::from(0u64), Fr::from(3u64)]);
+
+        // 2x + 5x + 4x^5 - 4x^5, the cancelling term leaves degree 1
+        let poly = DensePolynomial::from_terms(&[
+            (Fr::from(2u64), 1),
+            (Fr::from(5u64), 1),
+            (Fr::from(4u64), 5),
+            (-Fr::from(4u64), 5),
+        ]);
+        assert_eq!(poly.coefficients, vec![Fr::from(0u64), Fr::from(7u64)]);
+
+        let poly = DensePolynomial::from_terms(&[(Fr::from(1u64), 0), (Fr::from(9u64), 4)]);
+        assert_eq!(poly.degree(), 4);
+        assert_eq!(DensePolynomial::<Fr>::from_terms(&[]).coefficients, vec![Fr::from(0u64)]);
+    }
+
+    // sum of c_i * x^i with a fresh exponentiation per term, as `evaluate` used to do
+    fn evaluate_with_pow(poly: &DensePolynomial<Fr>, x: Fr) -> Fr {
+        poly.coefficients.iter().enumerate().map(|(i, &c)| c * x.pow([i as u64])).sum()
+    }
+
+    #[test]
+    fn test_evaluate_horner_matches_pow() {
+        let mut rng = ark_std::test_rng();
+        for _ in 0..10 {
+            let poly = DensePolynomial::new((0..21).map(|_| Fr::rand(&mut rng)).collect());
+            let x = Fr::rand(&mut rng);
+            assert_eq!(poly.evaluate_horner(x), evaluate_with_pow(&poly, x));
+            assert_eq!(poly.evaluate(x), poly.evaluate_horner(x));
+        }
+    }
+
+    // Prints pow-based against Horner evaluation times without asserting on them (test_evaluate_horner_matches_pow
+    // covers the values); `cargo test --release -- --ignored --nocapture bench_evaluate_horner`
+    #[test]
+    #[ignore]
+    fn bench_evaluate_horner_against_pow() {
+        let mut rng = ark_std::test_rng();
+        let poly = DensePolynomial::new((0..256).map(|_| Fr::rand(&mut rng)).collect());
+        let points: Vec<Fr> = (0..50).map(|_| Fr::rand(&mut rng)).collect();
+
+        let start = std::time::Instant::now();
+        points.iter().for_each(|&x| { std::hint::black_box(evaluate_with_pow(&poly, x)); });
+        let pow_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        points.iter().for_each(|&x| { std::hint::black_box(poly.evaluate_horner(x)); });
+        let horner_time = start.elapsed();
+
+        println!("degree 255, 50 points: pow {pow_time:?}, horner {horner_time:?}");
+    }
+
+    #[test]
+    fn test_derivative_and_integrate() {
+        // d/dx (5 + 3x + 2x^2 + x^3) = 3 + 4x + 3x^2
+        let poly = DensePolynomial::new(vec![Fr::from(5u64), Fr::from(3u64), Fr::from(2u64), Fr::from(1u64)]);
+        assert_eq!(poly.derivative().coefficients, vec![Fr::from(3u64), Fr::from(4u64), Fr::from(3u64)]);
+        assert_eq!(DensePolynomial::new(vec![Fr::from(7u64)]).derivative().coefficients, vec![Fr::from(0u64)]);
+
+        // ∫ (2 + 6x) = 2x + 3x^2
+        let linear = DensePolynomial::new(vec![Fr::from(2u64), Fr::from(6u64)]);
+        assert_eq!(linear.integrate().coefficients, vec![Fr::from(0u64), Fr::from(2u64), Fr::from(3u64)]);
+
+        let mut rng = ark_std::test_rng();
+        for len in [1, 2, 7, 20] {
+            let poly = DensePolynomial::new((0..len).map(|_| Fr::rand(&mut rng)).collect());
+            assert_eq!(poly.integrate().derivative().coefficients, poly.coefficients);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "integration needs the field characteristic to exceed the degree + 1")]
+    fn test_integrate_panics_when_characteristic_is_too_small() {
+        use ark_ff::fields::{Fp64, MontBackend, MontConfig};
+
+        #[derive(MontConfig)]
+        #[modulus = "5"]
+        #[generator = "2"]
+        struct F5Config;
+        type F5 = Fp64<MontBackend<F5Config, 1>>;
+
+        // fine up to degree 3, but x^4 would integrate to x^5 / 5
+        let cubic = DensePolynomial::new(vec![F5::from(1u64); 4]);
+        assert_eq!(cubic.integrate().derivative().coefficients, cubic.coefficients);
+        DensePolynomial::new(vec![F5::from(1u64); 5]).integrate();
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // (x^2 - 1) / (x - 1) = x + 1
+        let dividend = DensePolynomial::new(vec![-Fr::from(1u64), Fr::from(0u64), Fr::from(1u64)]);
+        let divisor = DensePolynomial::new(vec![-Fr::from(1u64), Fr::from(1u64)]);
+        let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+        assert_eq!(quotient.coefficients, vec![Fr::from(1u64), Fr::from(1u64)]);
+        assert_eq!(remainder.coefficients, vec![Fr::from(0u64)]);
+
+        let mut rng = ark_std::test_rng();
+        for (len, divisor_len) in [(8, 3), (5, 5), (2, 4), (6, 1)] {
+            let dividend = DensePolynomial::new((0..len).map(|_| Fr::rand(&mut rng)).collect());
+            let divisor = DensePolynomial::new((0..divisor_len).map(|_| Fr::rand(&mut rng)).collect());
+            let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+            assert!(remainder.degree_opt().is_none_or(|d| d < divisor.degree()));
+            assert_eq!((&(&quotient * &divisor) + &remainder).coefficients, dividend.coefficients);
+        }
+
+        let zero = DensePolynomial::new(vec![Fr::from(0u64), Fr::from(0u64)]);
+        assert_eq!(dividend.div_rem(&zero).unwrap_err(), DivError::DivideByZero);
+    }
+
+    #[test]
+    fn test_from_roots() {
+        let mut rng = ark_std::test_rng();
+        let roots: Vec<Fr> = (0..6).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = DensePolynomial::from_roots(&roots);
+        assert_eq!(poly.degree(), 6);
+        assert_eq!(*poly.coefficients.last().unwrap(), Fr::from(1u64));
+        assert!(roots.iter().all(|&r| poly.evaluate(r) == Fr::from(0u64)));
+        assert_ne!(poly.evaluate(Fr::rand(&mut rng)), Fr::from(0u64));
+
+        assert_eq!(DensePolynomial::<Fr>::from_roots(&[]).coefficients, vec![Fr::from(1u64)]);
+        // (X - 1)(X + 1) = X^2 - 1
+        let poly = DensePolynomial::from_roots(&[Fr::from(1u64), -Fr::from(1u64)]);
+        assert_eq!(poly.coefficients, vec![-Fr::from(1u64), Fr::from(0u64), Fr::from(1u64)]);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let points = [(Fr::from(0u64), Fr::from(3u64)), (Fr::from(1u64), Fr::from(17u64)), (Fr::from(2u64), -Fr::from(5u64))];
+        let poly = DensePolynomial::interpolate(&points);
+        let json = poly.to_json();
+        let parsed = DensePolynomial::<Fr>::from_json(&json).unwrap();
+        assert_eq!(parsed.coefficients, poly.coefficients);
+        let x = Fr::from(123456789u64);
+        assert_eq!(parsed.evaluate(x), poly.evaluate(x));
+
+        let small = DensePolynomial::new(vec![Fr::from(0u64), Fr::from(3u64), Fr::from(17u64)]);
+        assert_eq!(small.to_json(), r#"{"coefficients": ["0", "3", "17"]}"#);
+        let spaced = DensePolynomial::<Fr>::from_json(" { \"coefficients\" : [ \"0\" ,\"3\", \"17\" ] } ").unwrap();
+        assert_eq!(spaced.coefficients, small.coefficients);
+
+        assert_eq!(DensePolynomial::<Fr>::from_json("[\"1\"]").unwrap_err(), JsonError::Malformed);
+        assert_eq!(
+            DensePolynomial::<Fr>::from_json(r#"{"coefficients": ["1", "x"]}"#).unwrap_err(),
+            JsonError::InvalidCoefficient("\"x\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_canonical_serialization_round_trip() {
+        let cases = [
+            vec![Fr::from(1u64), Fr::from(2u64)],
+            vec![Fr::from(0u64)],
+            vec![Fr::from(42u64)],
+        ];
+        for coefficients in cases {
+            let poly = DensePolynomial::new(coefficients);
+            let mut bytes = vec![];
+            poly.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), poly.compressed_size());
+            assert_eq!(bytes[..8], (poly.coefficients.len() as u64).to_le_bytes());
+            let decoded = DensePolynomial::<Fr>::deserialize_compressed(&bytes[..]).unwrap();
+            assert_eq!(decoded.coefficients, poly.coefficients);
+        }
+
+        // an encoding of [1, 2, 0] decodes trimmed
+        let mut bytes = vec![];
+        vec![Fr::from(1u64), Fr::from(2u64), Fr::from(0u64)].serialize_compressed(&mut bytes).unwrap();
+        let decoded = DensePolynomial::<Fr>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(decoded.coefficients, vec![Fr::from(1u64), Fr::from(2u64)]);
+    }
+
+    #[test]
+    fn test_scalar_mul_and_neg() {
+        let poly = DensePolynomial::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)]);
+        assert_eq!(poly.scalar_mul(Fr::from(2u64)).coefficients, vec![Fr::from(2u64), Fr::from(4u64), Fr::from(6u64)]);
+        assert_eq!(poly.scalar_mul(Fr::from(0u64)).coefficients, vec![Fr::from(0u64)]);
+
+        let mut rng = ark_std::test_rng();
+        let poly = DensePolynomial::new((0..6).map(|_| Fr::rand(&mut rng)).collect());
+        assert_eq!(poly.neg().neg().coefficients, poly.coefficients);
+        assert_eq!((&poly + &poly.neg()).coefficients, vec![Fr::from(0u64)]);
+    }
+
+    #[test]
+    fn test_check_passes_through() {
+        // 1 + 2x
+        let poly = DensePolynomial::new(vec![Fr::from(1u64), Fr::from(2u64)]);
+        let mut points: Vec<(Fr, Fr)> = (0..5u64).map(|x| (Fr::from(x), Fr::from(1 + 2 * x))).collect();
+        assert!(poly.check_passes_through(&points).is_empty());
+
+        points[3].1 += Fr::from(1u64);
+        assert_eq!(poly.check_passes_through(&points), vec![3]);
+    }
+
+    #[test]
+    fn test_degree_opt() {
+        assert_eq!(DensePolynomial::new(vec![Fr::from(0u64)]).degree_opt(), None);
+        assert_eq!(DensePolynomial::<Fr> { coefficients: vec![] }.degree_opt(), None);
+        assert_eq!(DensePolynomial::new(vec![Fr::from(0u64)]).degree(), 0);
+
+        assert_eq!(DensePolynomial::new(vec![Fr::from(7u64)]).degree_opt(), Some(0));
+        assert_eq!(DensePolynomial::new(vec![Fr::from(7u64), Fr::from(0u64), Fr::from(2u64)]).degree_opt(), Some(2));
+    }
+}
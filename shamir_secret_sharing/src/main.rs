@@ -1,6 +1,10 @@
-use ark_ff::PrimeField;
-use prime_polynomail::{self, DensePolynomial};
+use ark_ec::PrimeGroup;
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use prime_polynomail::{self, BarycentricPoly, DensePolynomial};
 use rand;
+use sha3::{Digest, Keccak256};
+use transcript::transcript::{KeccakWrapper, Transcript};
 
 fn create_polynomial<F: PrimeField>(secret: F, degree: usize) -> DensePolynomial<F> {
     let mut random_value = rand::thread_rng();
@@ -14,8 +18,24 @@ fn create_polynomial<F: PrimeField>(secret: F, degree: usize) -> DensePolynomial
     }
 }
 
-fn split_secret<F: PrimeField>(secret: F, total_shares: usize, threshold: usize) -> Vec<(F, F)> {
-    let poly = create_polynomial(secret, threshold - 1);
+// Like `create_polynomial`, but the random coefficients are squeezed from a transcript seeded with `seed`.
+// Each coefficient's index is absorbed before its squeeze so consecutive coefficients differ.
+fn create_polynomial_from_seed<F: PrimeField>(secret: F, degree: usize, seed: &[u8]) -> DensePolynomial<F> {
+    let mut transcript = Transcript::<KeccakWrapper, F>::new(KeccakWrapper { keccak: Keccak256::new() });
+    transcript.absorb(seed);
+    let mut coeffs = vec![secret];
+
+    for i in 0..degree {
+        transcript.absorb(&(i as u64).to_be_bytes());
+        coeffs.push(transcript.squeeze());
+    }
+    DensePolynomial {
+        coefficients: coeffs,
+    }
+}
+
+// Shares (1, p(1)), ..., (total_shares, p(total_shares))
+fn evaluate_shares<F: PrimeField>(poly: &DensePolynomial<F>, total_shares: usize) -> Vec<(F, F)> {
     let mut all_shares = Vec::new();
     for i in 1..=total_shares {
         let x = F::from(i as u64);
@@ -25,20 +45,151 @@ fn split_secret<F: PrimeField>(secret: F, total_shares: usize, threshold: usize)
     all_shares
 }
 
+#[derive(Debug, Clone, PartialEq)]
+enum ShareError {
+    // fewer shares than the threshold, so the secret could never be recovered
+    ThresholdExceedsTotal { threshold: usize, total_shares: usize },
+    // a zero threshold leaves no sharing polynomial to hide the secret in
+    ZeroThreshold,
+}
+
+// Every splitter needs 1 <= threshold <= total_shares
+fn check_threshold(total_shares: usize, threshold: usize) -> Result<(), ShareError> {
+    if threshold == 0 {
+        return Err(ShareError::ZeroThreshold);
+    }
+    if threshold > total_shares {
+        return Err(ShareError::ThresholdExceedsTotal { threshold, total_shares });
+    }
+    Ok(())
+}
+
+fn split_secret<F: PrimeField>(secret: F, total_shares: usize, threshold: usize) -> Result<Vec<(F, F)>, ShareError> {
+    check_threshold(total_shares, threshold)?;
+    let poly = create_polynomial(secret, threshold - 1);
+    Ok(evaluate_shares(&poly, total_shares))
+}
+
+// Same seed, same shares: for reproducible test vectors and key-ceremony tooling
+fn split_secret_deterministic<F: PrimeField>(secret: F, total_shares: usize, threshold: usize, seed: &[u8]) -> Result<Vec<(F, F)>, ShareError> {
+    check_threshold(total_shares, threshold)?;
+    let poly = create_polynomial_from_seed(secret, threshold - 1, seed);
+    Ok(evaluate_shares(&poly, total_shares))
+}
+
 fn recover_secret<F: PrimeField>(shares: &[(F, F)], threshold: usize) -> F {
-    let points = &shares[..threshold];
-    let poly = DensePolynomial::interpolate(points);
-    poly.evaluate(F::zero())
+    // only p(0) is needed, so skip expanding the coefficients
+    BarycentricPoly::new(&shares[..threshold]).evaluate(F::zero())
+}
+
+// Same as `recover_secret`, but checks that the shares not used for interpolation lie on the
+// recovered polynomial, returning None if any of them disagrees.
+fn recover_secret_checked<F: PrimeField>(shares: &[(F, F)], threshold: usize) -> Option<F> {
+    let poly = DensePolynomial::interpolate(&shares[..threshold]);
+    if !poly.check_passes_through(&shares[threshold..]).is_empty() {
+        return None;
+    }
+    Some(poly.evaluate(F::zero()))
+}
+
+type Share<G> = (<G as PrimeGroup>::ScalarField, <G as PrimeGroup>::ScalarField);
+
+// Feldman VSS: split the secret and publish a commitment g^a_i to every coefficient of the sharing polynomial
+fn split_secret_feldman<G: PrimeGroup>(secret: G::ScalarField, total_shares: usize, threshold: usize) -> Result<(Vec<Share<G>>, Vec<G>), ShareError> {
+    check_threshold(total_shares, threshold)?;
+    let poly = create_polynomial(secret, threshold - 1);
+    let commitments = poly.coefficients.iter().map(|&coeff| G::generator() * coeff).collect();
+    let shares = (1..=total_shares)
+        .map(|i| {
+            let x = G::ScalarField::from(i as u64);
+            (x, poly.evaluate(x))
+        })
+        .collect();
+    Ok((shares, commitments))
+}
+
+// A share (x, y) is valid if g^y == prod_i C_i^(x^i)
+fn verify_share<G: PrimeGroup>(share: &Share<G>, commitments: &[G]) -> bool {
+    let (x, y) = *share;
+    let mut x_power = G::ScalarField::from(1u64);
+    let mut expected = G::zero();
+    for commitment in commitments {
+        expected += *commitment * x_power;
+        x_power *= x;
+    }
+    G::generator() * y == expected
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DkgError {
+    // one share is expected from every dealer
+    ShareCountMismatch { dealers: usize, shares: usize },
+    // the share from `dealer` does not match its commitments
+    InvalidShare { dealer: usize },
+}
+
+// One DKG round from a single party's point of view: `commitments[i]` is dealer i's Feldman commitment
+// vector and `shares_received[i]` the share dealer i sent this party. All shares are checked at once against
+// a random linear combination drawn from a transcript of the whole round, and only checked one by one to
+// name the bad dealer if that fails. Returns the group public key, the sum of every dealer's g^secret.
+fn aggregate_dkg<G: PrimeGroup>(commitments: &[Vec<G>], shares_received: &[Share<G>]) -> Result<G, DkgError> {
+    if commitments.len() != shares_received.len() {
+        return Err(DkgError::ShareCountMismatch { dealers: commitments.len(), shares: shares_received.len() });
+    }
+
+    let mut transcript = Transcript::<KeccakWrapper, G::ScalarField>::new(KeccakWrapper { keccak: Keccak256::new() });
+    for (dealer_commitments, (x, y)) in commitments.iter().zip(shares_received) {
+        let mut bytes = vec![];
+        dealer_commitments.serialize_compressed(&mut bytes).unwrap();
+        bytes.extend(x.into_bigint().to_bytes_be());
+        bytes.extend(y.into_bigint().to_bytes_be());
+        transcript.absorb(&bytes);
+    }
+    let r = transcript.squeeze();
+
+    // g^(Σ r^i y_i) == Σ r^i Π_k C_ik^(x_i^k)
+    let mut r_power = G::ScalarField::from(1u64);
+    let mut combined_y = G::ScalarField::from(0u64);
+    let mut combined_expected = G::zero();
+    for (dealer_commitments, (x, y)) in commitments.iter().zip(shares_received) {
+        let mut x_power = G::ScalarField::from(1u64);
+        for commitment in dealer_commitments {
+            combined_expected += *commitment * (r_power * x_power);
+            x_power *= x;
+        }
+        combined_y += r_power * y;
+        r_power *= r;
+    }
+
+    if G::generator() * combined_y != combined_expected {
+        let dealer = (0..commitments.len())
+            .find(|&i| !verify_share(&shares_received[i], &commitments[i]))
+            .unwrap_or(0);
+        return Err(DkgError::InvalidShare { dealer });
+    }
+
+    Ok(commitments.iter().map(|dealer_commitments| dealer_commitments[0]).sum())
 }
 
 fn main() {
     println!("Hello, world!");
 }
 
+#[cfg(test)]
+mod testutil {
+    use ark_ff::PrimeField;
+
+    // Simulates a dishonest shareholder by shifting the y-value of the share at `index` by `delta`
+    pub fn corrupt_share<F: PrimeField>(shares: &mut [(F, F)], index: usize, delta: F) {
+        shares[index].1 += delta;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_bn254::Fr;
+    use super::testutil::corrupt_share;
+    use ark_bn254::{Fr, G1Projective};
 
     #[test]
     fn test_create_poly() {
@@ -50,8 +201,118 @@ mod tests {
     #[test]
     fn test_split_secret() {
         let secret = Fr::from(12345u64);
-        let shares = split_secret(secret, 5, 3);
+        let shares = split_secret(secret, 5, 3).unwrap();
         let recover_secret = recover_secret(&shares, 3);
         assert_eq!(recover_secret, secret);
     }
+
+    #[test]
+    fn test_split_secret_rejects_bad_threshold() {
+        let secret = Fr::from(12345u64);
+        assert_eq!(split_secret(secret, 3, 5), Err(ShareError::ThresholdExceedsTotal { threshold: 5, total_shares: 3 }));
+        assert_eq!(split_secret(secret, 3, 0), Err(ShareError::ZeroThreshold));
+        assert_eq!(
+            split_secret_deterministic(secret, 3, 5, b"ceremony 1"),
+            Err(ShareError::ThresholdExceedsTotal { threshold: 5, total_shares: 3 })
+        );
+        assert!(split_secret_feldman::<G1Projective>(secret, 3, 5).is_err());
+
+        // threshold == total_shares is the tightest valid split
+        let shares = split_secret(secret, 3, 3).unwrap();
+        assert_eq!(recover_secret(&shares, 3), secret);
+        let shares = split_secret(secret, 3, 1).unwrap();
+        assert_eq!(recover_secret(&shares, 1), secret);
+    }
+
+    #[test]
+    fn test_split_secret_deterministic() {
+        let secret = Fr::from(12345u64);
+        let shares = split_secret_deterministic(secret, 5, 3, b"ceremony 1").unwrap();
+        assert_eq!(shares, split_secret_deterministic(secret, 5, 3, b"ceremony 1").unwrap());
+        assert_ne!(shares, split_secret_deterministic(secret, 5, 3, b"ceremony 2").unwrap());
+        assert_eq!(recover_secret(&shares, 3), secret);
+
+        let poly = create_polynomial_from_seed(secret, 2, b"ceremony 1");
+        assert_ne!(poly.coefficients[1], poly.coefficients[2]);
+    }
+
+    #[test]
+    fn test_recover_secret_checked() {
+        let secret = Fr::from(12345u64);
+        let shares = split_secret(secret, 5, 3).unwrap();
+        assert_eq!(recover_secret_checked(&shares, 3), Some(secret));
+    }
+
+    #[test]
+    fn test_feldman_verify_share() {
+        let secret = Fr::from(12345u64);
+        let (shares, commitments) = split_secret_feldman::<G1Projective>(secret, 5, 3).unwrap();
+        assert!(shares.iter().all(|share| verify_share(share, &commitments)));
+        assert_eq!(recover_secret(&shares, 3), secret);
+    }
+
+    // every party deals its secret to all three; party j receives shares[i][j] from dealer i
+    fn deal_dkg_round(secrets: &[Fr]) -> (Vec<Vec<Share<G1Projective>>>, Vec<Vec<G1Projective>>) {
+        secrets.iter().map(|&secret| split_secret_feldman::<G1Projective>(secret, 3, 2).unwrap()).unzip()
+    }
+
+    #[test]
+    fn test_aggregate_dkg() {
+        let secrets = [Fr::from(11u64), Fr::from(22u64), Fr::from(33u64)];
+        let (shares, commitments) = deal_dkg_round(&secrets);
+        let group_secret: Fr = secrets.iter().sum();
+
+        let mut combined_shares = vec![];
+        for party in 0..3 {
+            let received: Vec<_> = shares.iter().map(|dealer_shares| dealer_shares[party]).collect();
+            assert_eq!(aggregate_dkg(&commitments, &received), Ok(G1Projective::generator() * group_secret));
+            combined_shares.push((received[0].0, received.iter().map(|(_, y)| *y).sum::<Fr>()));
+        }
+
+        // the summed shares are a sharing of the summed secrets
+        assert_eq!(recover_secret(&combined_shares, 2), group_secret);
+    }
+
+    #[test]
+    fn test_aggregate_dkg_names_bad_dealer() {
+        let secrets = [Fr::from(11u64), Fr::from(22u64), Fr::from(33u64)];
+        let (shares, commitments) = deal_dkg_round(&secrets);
+        let mut received: Vec<_> = shares.iter().map(|dealer_shares| dealer_shares[0]).collect();
+
+        assert_eq!(
+            aggregate_dkg(&commitments, &received[..2]),
+            Err(DkgError::ShareCountMismatch { dealers: 3, shares: 2 })
+        );
+
+        corrupt_share(&mut received, 1, Fr::from(5u64));
+        assert_eq!(aggregate_dkg(&commitments, &received), Err(DkgError::InvalidShare { dealer: 1 }));
+    }
+
+    #[test]
+    fn test_single_corrupted_share_is_detected() {
+        let secret = Fr::from(12345u64);
+        let (mut shares, commitments) = split_secret_feldman::<G1Projective>(secret, 5, 3).unwrap();
+        corrupt_share(&mut shares, 1, Fr::from(7u64));
+
+        // plain recovery silently returns the wrong secret
+        assert_ne!(recover_secret(&shares, 3), secret);
+        assert_eq!(recover_secret_checked(&shares, 3), None);
+
+        let invalid: Vec<usize> = (0..shares.len()).filter(|&i| !verify_share(&shares[i], &commitments)).collect();
+        assert_eq!(invalid, vec![1]);
+    }
+
+    #[test]
+    fn test_two_corrupted_shares_are_detected() {
+        let secret = Fr::from(12345u64);
+        let (mut shares, commitments) = split_secret_feldman::<G1Projective>(secret, 5, 3).unwrap();
+        corrupt_share(&mut shares, 0, Fr::from(3u64));
+        corrupt_share(&mut shares, 4, Fr::from(11u64));
+
+        assert_ne!(recover_secret(&shares, 3), secret);
+        assert_eq!(recover_secret_checked(&shares, 3), None);
+
+        let invalid: Vec<usize> = (0..shares.len()).filter(|&i| !verify_share(&shares[i], &commitments)).collect();
+        assert_eq!(invalid, vec![0, 4]);
+    }
 }
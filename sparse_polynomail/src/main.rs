@@ -1,30 +1,164 @@
-struct SparsePolynomial{
-    coefficients: Vec<(u32,u32)>,
-    degree: u32,
+use ark_ff::PrimeField;
+use prime_polynomail::DensePolynomial;
+use std::collections::HashMap;
+use std::ops::{Add, Mul};
+
+struct SparsePolynomial<F: PrimeField> {
+    // (coefficient, exponent), sorted by exponent with no repeats
+    coefficients: Vec<(F, usize)>,
+    degree: usize,
 }
 
-impl SparsePolynomial{
-    fn new(coefficients: Vec<(u32,u32)>) -> SparsePolynomial{
-        let degree = *coefficients.iter().map(|(_, d)| d).max().unwrap();
-        SparsePolynomial{coefficients: coefficients, degree: degree}
+impl<F: PrimeField> SparsePolynomial<F> {
+    // Terms with a repeated exponent are summed and zero coefficients dropped, so the zero polynomial is
+    // always the empty term list
+    fn new(coefficients: Vec<(F, usize)>) -> SparsePolynomial<F> {
+        let mut sorted = coefficients;
+        sorted.sort_by_key(|&(_, d)| d);
+        let mut merged: Vec<(F, usize)> = Vec::with_capacity(sorted.len());
+        for (c, d) in sorted {
+            match merged.last_mut() {
+                Some(last) if last.1 == d => last.0 += c,
+                _ => merged.push((c, d)),
+            }
+        }
+        merged.retain(|(c, _)| !c.is_zero());
+        let degree = merged.last().map_or(0, |&(_, d)| d);
+        SparsePolynomial { coefficients: merged, degree }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
     }
 
-    fn degree(&self) -> u32{
+    fn degree(&self) -> usize {
         self.degree
     }
 
-    fn evaluate(&self, x: u32) -> u32 {
-        let result = self.coefficients.iter().map(|(c,d)| c * x.pow(*d)).sum();
-        return result;
+    fn evaluate(&self, x: F) -> F {
+        self.coefficients.iter().map(|(c, d)| *c * x.pow([*d as u64])).sum()
+    }
+
+    fn to_dense(&self) -> DensePolynomial<F> {
+        DensePolynomial::from_terms(&self.coefficients)
     }
 
+    fn from_dense(poly: &DensePolynomial<F>) -> SparsePolynomial<F> {
+        SparsePolynomial::new(poly.coefficients.iter().enumerate().map(|(d, &c)| (c, d)).collect())
+    }
 }
 
+// `new` merges the matching exponents and drops whatever cancels
+impl<F: PrimeField> Add for SparsePolynomial<F> {
+    type Output = Self;
 
+    fn add(self, other: Self) -> Self {
+        let mut terms = self.coefficients;
+        terms.extend(other.coefficients);
+        SparsePolynomial::new(terms)
+    }
+}
 
+impl<F: PrimeField> Mul for SparsePolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let mut by_exponent: HashMap<usize, F> = HashMap::new();
+        for &(a, i) in self.coefficients.iter() {
+            for &(b, j) in other.coefficients.iter() {
+                *by_exponent.entry(i + j).or_insert(F::zero()) += a * b;
+            }
+        }
+        SparsePolynomial::new(by_exponent.into_iter().map(|(d, c)| (c, d)).collect())
+    }
+}
 
 fn main() {
     println!("Hello, world!");
-    let result = SparsePolynomial::new(vec![(2,1),(5,0)]);
+    let result = SparsePolynomial::new(vec![(ark_bn254::Fr::from(2u64), 1), (ark_bn254::Fr::from(5u64), 0)]);
     println!("The degree is: {:?}", result.degree());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_zero_is_empty() {
+        for terms in [vec![], vec![(Fr::from(0u64), 0)], vec![(Fr::from(0u64), 3), (Fr::from(0u64), 1)]] {
+            let zero = SparsePolynomial::new(terms);
+            assert!(zero.is_zero());
+            assert!(zero.coefficients.is_empty());
+            assert_eq!(zero.degree(), 0);
+            assert_eq!(zero.evaluate(Fr::from(7u64)), Fr::from(0u64));
+        }
+    }
+
+    #[test]
+    fn test_zero_terms_are_dropped() {
+        let poly = SparsePolynomial::new(vec![(Fr::from(2u64), 1), (Fr::from(0u64), 4), (Fr::from(5u64), 0)]);
+        assert!(!poly.is_zero());
+        assert_eq!(poly.coefficients, vec![(Fr::from(5u64), 0), (Fr::from(2u64), 1)]);
+        assert_eq!(poly.degree(), 1);
+        assert_eq!(poly.evaluate(Fr::from(3u64)), Fr::from(11u64));
+    }
+
+    #[test]
+    fn test_terms_are_sorted_and_merged() {
+        // 3x^5 + 1 + 4x^5 - 7x^5 + 2x^2 = 1 + 2x^2
+        let poly = SparsePolynomial::new(vec![
+            (Fr::from(3u64), 5),
+            (Fr::from(1u64), 0),
+            (Fr::from(4u64), 5),
+            (-Fr::from(7u64), 5),
+            (Fr::from(2u64), 2),
+        ]);
+        assert_eq!(poly.coefficients, vec![(Fr::from(1u64), 0), (Fr::from(2u64), 2)]);
+        assert_eq!(poly.degree(), 2);
+    }
+
+    #[test]
+    fn test_add_and_mul_match_dense() {
+        let a = SparsePolynomial::new(vec![(Fr::from(3u64), 0), (Fr::from(2u64), 7), (-Fr::from(5u64), 20)]);
+        let b = SparsePolynomial::new(vec![(Fr::from(1u64), 1), (Fr::from(5u64), 20)]);
+        let (dense_a, dense_b) = (a.to_dense(), b.to_dense());
+
+        let sum = SparsePolynomial::new(a.coefficients.clone()) + SparsePolynomial::new(b.coefficients.clone());
+        // the x^20 terms cancel
+        assert_eq!(sum.degree(), 7);
+        assert_eq!(sum.to_dense().coefficients, (&dense_a + &dense_b).coefficients);
+
+        let product = a * b;
+        assert_eq!(product.degree(), 40);
+        assert_eq!(product.to_dense().coefficients, (&dense_a * &dense_b).coefficients);
+        assert!(product.coefficients.windows(2).all(|w| w[0].1 < w[1].1));
+    }
+
+    #[test]
+    fn test_add_cancelling_to_zero() {
+        let a = SparsePolynomial::new(vec![(Fr::from(4u64), 2), (Fr::from(9u64), 5)]);
+        let negated = SparsePolynomial::new(a.coefficients.iter().map(|&(c, d)| (-c, d)).collect());
+        let sum = a + negated;
+        assert!(sum.is_zero());
+        assert_eq!(sum.degree(), 0);
+
+        let zero = SparsePolynomial::new(vec![]);
+        assert!((SparsePolynomial::new(vec![(Fr::from(4u64), 2)]) * zero).is_zero());
+    }
+
+    #[test]
+    fn test_evaluation_matches_dense() {
+        // large coefficients that would overflow u32
+        let poly = SparsePolynomial::new(vec![(-Fr::from(1u64), 40), (Fr::from(u64::MAX), 3), (Fr::from(9u64), 0)]);
+        let dense = poly.to_dense();
+        assert_eq!(dense.coefficients.len(), 41);
+        for x in [0u64, 1, 2, 12345] {
+            assert_eq!(poly.evaluate(Fr::from(x)), dense.evaluate(Fr::from(x)));
+        }
+
+        let back = SparsePolynomial::from_dense(&dense);
+        assert_eq!(back.coefficients, poly.coefficients);
+        assert!(SparsePolynomial::from_dense(&DensePolynomial::new(vec![Fr::from(0u64)])).is_zero());
+    }
+}
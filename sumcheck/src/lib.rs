@@ -1 +1,2 @@
-pub mod sumcheck;
\ No newline at end of file
+pub mod sumcheck;
+pub mod transcript;
\ No newline at end of file
@@ -1,10 +1,11 @@
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::PrimeField;
 use multilinear::multilinear::MultivariatePoly;
 use multilinear::composite::{Composite, OP};
 use prime_polynomail::{self, DensePolynomial};
 use transcript::transcript::{HashTrait, Transcript, TranscriptTrait};
 use std::marker::PhantomData;
 use std::iter::repeat_n;
+use sumcheck::sumcheck::next_challenge;
 
 /// The Sum-Check protocol is a protocol for verifying that the sum of a polynomial over a
 /// boolean hypercube is equal to a claimed value.
@@ -46,9 +47,7 @@ pub fn generate_partial_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>
         final_eval = round_poly[0] + round_poly[1];
         // dbg!(&round_poly, final_eval);        
         partial_evals.push(final_eval);
-        let mut data = vec![final_eval];
-        data.extend(&round_poly);
-        let challenge = add_data_to_transcript::<F, H, T>(&data, transcript);
+        let challenge = next_challenge::<F, H, T>(transcript, &round_poly, final_eval);
         // dbg!(&challenge);
 
         challenges.push(challenge);
@@ -74,11 +73,7 @@ pub fn verify_partial_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(
             panic!("Invalid proof");
             return (F::zero(), vec![]);
         }        
-        let mut data = vec![final_sum];
-        data.extend(&round_poly.coefficients);
-        let challenge
-        = add_data_to_transcript::<F, H, T>(&data, transcript);
-        dbg!(&data);
+        let challenge = next_challenge::<F, H, T>(transcript, &round_poly.coefficients, final_sum);
         challenges.push(challenge);
 
         let points = round_polys[i].coefficients.iter().enumerate().map( |x| (F::from(x.0 as u64), x.1.clone())).collect::<Vec<(F, F)>>();
@@ -102,10 +97,7 @@ pub fn verify_partial_proof_2<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>
             panic!("Invalid proof for partial sum check");
         }
 
-        let mut data = vec![sum];
-        data.extend(&polys[i]);
-        challenge = add_data_to_transcript::<F, H, T>(&data, transcript);
-        dbg!(&data);
+        challenge = next_challenge::<F, H, T>(transcript, &polys[i], sum);
         challenges.push(challenge);
 
         let points = polys[i].iter().enumerate().map( |x| (F::from(x.0 as u64), x.1.clone())).collect::<Vec<(F, F)>>();
@@ -121,15 +113,8 @@ pub fn verify_partial_proof_2<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>
 }
 
 pub fn add_data_to_transcript <F: PrimeField, H: HashTrait, T: TranscriptTrait<F>> (data: &Vec<F>, transcript: &mut T) -> F {
-    let mut bytes = vec![];
-    data.iter().for_each(|x| {
-        bytes.extend(x.into_bigint().to_bytes_be())
-    });
-    transcript.absorb(&bytes);
-    let squeezed = transcript.squeeze();
-    let squeezed_bytes = squeezed.into_bigint().to_bytes_be();
-    let challenge = F::from_be_bytes_mod_order(&squeezed_bytes);
-    return challenge;
+    transcript.absorb_field_slice(data);
+    transcript.challenge()
 }
 
 
@@ -1,42 +1,88 @@
 use ark_ff::{BigInteger, PrimeField};
 use multilinear::multilinear::MultivariatePoly;
-use multilinear::composite::{Composite, OP};
+use multilinear::composite::SumcheckPoly;
 use prime_polynomail::{self, DensePolynomial};
 use transcript::transcript::{HashTrait, Transcript, TranscriptTrait};
 use std::marker::PhantomData;
-use std::iter::repeat_n;
+
+// A complete non-interactive sumcheck proof: the claimed sum, one round polynomial per variable and
+// the polynomial's evaluation at the final challenges
+#[derive(Debug, Clone)]
+pub struct SumCheckProof<F: PrimeField> {
+    pub claimed_sum: F,
+    // degree of every round polynomial, each sent as its degree + 1 evaluations
+    pub degree: usize,
+    pub round_polys: Vec<DensePolynomial<F>>,
+    pub final_evaluation: F,
+}
+
+impl<F: PrimeField> SumCheckProof<F> {
+    // Every field element takes the full width of its big integer
+    const ELEMENT_BYTES: usize = <F::BigInt as BigInteger>::NUM_LIMBS * 8;
+
+    // claimed sum || degree || round count || (coefficient count || coefficients) per round || final evaluation,
+    // with degree and counts as u64 and field elements big-endian
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.claimed_sum.into_bigint().to_bytes_be();
+        bytes.extend((self.degree as u64).to_be_bytes());
+        bytes.extend((self.round_polys.len() as u64).to_be_bytes());
+        for round_poly in self.round_polys.iter() {
+            bytes.extend((round_poly.coefficients.len() as u64).to_be_bytes());
+            round_poly.coefficients.iter().for_each(|x| bytes.extend(x.into_bigint().to_bytes_be()));
+        }
+        bytes.extend(self.final_evaluation.into_bigint().to_bytes_be());
+        bytes
+    }
+
+    // Size of `to_bytes` without serializing
+    pub fn proof_size_bytes(&self) -> usize {
+        let coefficients: usize = self.round_polys.iter().map(|p| p.coefficients.len()).sum();
+        (2 + coefficients) * Self::ELEMENT_BYTES + (2 + self.round_polys.len()) * 8
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SumCheckError {
+    // p(0) + p(1) of this round's polynomial isn't the claim carried over from the previous round
+    RoundSumMismatch { round: usize },
+    // the round polynomial has a higher degree than the verifier allows
+    RoundDegreeTooHigh { round: usize, degree: usize, max_degree: usize },
+    // the claim left after the last round isn't the proof's final evaluation
+    FinalSumMismatch,
+    // the oracle disagrees with the proof's final evaluation at the challenges
+    OracleMismatch,
+}
+
+// Runs the prover and packages the result with the final evaluation the verifier checks against
+pub fn generate_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(poly: &impl SumcheckPoly<F>, transcript: &mut T) -> SumCheckProof<F> {
+    let mut round_polys = vec![];
+    let mut challenges = vec![];
+    let claimed_sum = generate_partial_proof::<F, H, T>(poly, transcript, &mut round_polys, &mut challenges);
+    let final_evaluation = poly.evaluate_at(&challenges);
+    SumCheckProof { claimed_sum, degree: poly.degree(), round_polys, final_evaluation }
+}
 
 /// The Sum-Check protocol is a protocol for verifying that the sum of a polynomial over a
 /// boolean hypercube is equal to a claimed value.
 /// 
-pub fn generate_partial_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(poly: &Composite<F>, transcript: &mut T, round_polys: &mut Vec<DensePolynomial<F>>,  challenges: &mut Vec<F>) -> F {
+pub fn generate_partial_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(poly: &impl SumcheckPoly<F>, transcript: &mut T, round_polys: &mut Vec<DensePolynomial<F>>,  challenges: &mut Vec<F>) -> F {
     let mut poly_eval = poly.clone();
-    let degree = 2;
-    let rounds = poly_eval.polys[0].num_vars as usize;   
+    // a degree d round polynomial needs d + 1 evaluations: 0 and 1 come from the hypercube, the rest are sampled
+    let degree = poly.degree();
+    let rounds = poly_eval.num_vars();   
     print!("rounds={:?}", rounds); 
     let mut partial_evals = vec![];
     let mut final_eval = F::zero();
 
-    for i in 0..rounds {
+    for _ in 0..rounds {
         let mut reduced_poly = poly_eval.reduce();
         let extra_points = reduced_poly.coeffs.len()/2;
-        let mut index = 0;
-
-        repeat_n(0, extra_points).for_each(|_| {
-            let mut values = vec![Some(F::zero()); rounds-i];
-            values = values.iter().enumerate().map( |x| {
-                if x.0 == 0 {
-                    return Some(F::from(2));
-                } else {
-                    // shift to right and find modulus to get the value at that point.
-                    return Some(F::from(index >> (rounds-i - x.0 - 1) & 1));
-                }
-            }).collect();
-
-            let result = poly_eval.evaluate(&values);
-            reduced_poly.coeffs.push(result);
-            index += 1;
-        });
+
+        // the first variable fixed at each sample point leaves a table over the rest of the hypercube, already
+        // in the order the round sums below read it
+        for point in 2..=degree {
+            reduced_poly.coeffs.extend(poly_eval.bind_first(F::from(point as u64)).reduce().coeffs);
+        }
 
         let mut round_poly = vec![];
         for j in 0..(degree + 1) {
@@ -46,14 +92,12 @@ pub fn generate_partial_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>
         final_eval = round_poly[0] + round_poly[1];
         // dbg!(&round_poly, final_eval);        
         partial_evals.push(final_eval);
-        let mut data = vec![final_eval];
-        data.extend(&round_poly);
-        let challenge = add_data_to_transcript::<F, H, T>(&data, transcript);
+        let challenge = next_challenge::<F, H, T>(transcript, &round_poly, final_eval);
         // dbg!(&challenge);
 
         challenges.push(challenge);
 
-        poly_eval = poly_eval.partial_evaluate(&vec![challenge], 0);
+        poly_eval = poly_eval.bind_first(challenge);
         round_polys.push(DensePolynomial { coefficients: round_poly });
 
     }
@@ -74,18 +118,11 @@ pub fn verify_partial_proof<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(
             panic!("Invalid proof");
             return (F::zero(), vec![]);
         }        
-        let mut data = vec![final_sum];
-        data.extend(&round_poly.coefficients);
-        let challenge
-        = add_data_to_transcript::<F, H, T>(&data, transcript);
-        dbg!(&data);
+        let challenge = next_challenge::<F, H, T>(transcript, &round_poly.coefficients, final_sum);
         challenges.push(challenge);
 
-        let points = round_polys[i].coefficients.iter().enumerate().map( |x| (F::from(x.0 as u64), x.1.clone())).collect::<Vec<(F, F)>>();
-        let univariate_poly = DensePolynomial::interpolate(&points);
+        let univariate_poly = round_poly_to_dense(&round_polys[i].coefficients);
         dbg!(&univariate_poly);
-        print!("================>>>>>>>>>>>>>>");
-        dbg!(&points);
         final_sum = univariate_poly.evaluate(challenge);
         dbg!(&final_sum, challenge);
     }
@@ -102,17 +139,12 @@ pub fn verify_partial_proof_2<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>
             panic!("Invalid proof for partial sum check");
         }
 
-        let mut data = vec![sum];
-        data.extend(&polys[i]);
-        challenge = add_data_to_transcript::<F, H, T>(&data, transcript);
-        dbg!(&data);
+        challenge = next_challenge::<F, H, T>(transcript, &polys[i], sum);
         challenges.push(challenge);
 
-        let points = polys[i].iter().enumerate().map( |x| (F::from(x.0 as u64), x.1.clone())).collect::<Vec<(F, F)>>();
-        let univariate_poly = DensePolynomial::interpolate(&points);
+        let univariate_poly = round_poly_to_dense(&polys[i]);
         println!("Univariate poly from Nonse");
         dbg!(&univariate_poly);
-        dbg!(&points);
         sum = DensePolynomial::evaluate(&univariate_poly, challenge);
         dbg!(&sum, challenge);
     }
@@ -120,16 +152,76 @@ pub fn verify_partial_proof_2<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>
     (sum, challenges, true)
 }
 
+// Verifies `proof` with the polynomial only available through `oracle`, which is queried once at the
+// final challenges, e.g. by opening a commitment there. Round polynomials are held to the proof's own degree.
+pub fn verify_with_oracle<F, H, T, O>(proof: &SumCheckProof<F>, transcript: &mut T, oracle: O) -> Result<(), SumCheckError>
+where
+    F: PrimeField,
+    H: HashTrait,
+    T: TranscriptTrait<F>,
+    O: Fn(&[F]) -> F,
+{
+    verify_with_degree_bound::<F, H, T, O>(proof, transcript, proof.degree, oracle)
+}
+
+// `verify_with_oracle` with the round degree bound chosen by the verifier: 1 for a multilinear polynomial,
+// 2 for a product of two, and so on
+pub fn verify_with_degree_bound<F, H, T, O>(proof: &SumCheckProof<F>, transcript: &mut T, max_degree: usize, oracle: O) -> Result<(), SumCheckError>
+where
+    F: PrimeField,
+    H: HashTrait,
+    T: TranscriptTrait<F>,
+    O: Fn(&[F]) -> F,
+{
+    let mut sum = proof.claimed_sum;
+    let mut challenges = vec![];
+    for (round, round_poly) in proof.round_polys.iter().enumerate() {
+        let evals = &round_poly.coefficients;
+        if evals.len() < 2 || sum != evals[0] + evals[1] {
+            return Err(SumCheckError::RoundSumMismatch { round });
+        }
+        let round_poly = round_poly_to_dense(evals);
+        if round_poly.degree() > max_degree {
+            return Err(SumCheckError::RoundDegreeTooHigh { round, degree: round_poly.degree(), max_degree });
+        }
+        let challenge = next_challenge::<F, H, T>(transcript, evals, sum);
+        challenges.push(challenge);
+        sum = round_poly.evaluate(challenge);
+    }
+
+    if sum != proof.final_evaluation {
+        return Err(SumCheckError::FinalSumMismatch);
+    }
+    if oracle(&challenges) != proof.final_evaluation {
+        return Err(SumCheckError::OracleMismatch);
+    }
+    Ok(())
+}
+
+// A round message holds the round polynomial's evaluations at 0, 1, 2, ...; this interpolates them into
+// coefficient form
+pub fn round_poly_to_dense<F: PrimeField>(evals: &[F]) -> DensePolynomial<F> {
+    let points: Vec<(F, F)> = evals.iter().enumerate().map(|(x, &y)| (F::from(x as u64), y)).collect();
+    DensePolynomial::interpolate(&points)
+}
+
+// Big-endian bytes of every element in turn
+pub fn field_bytes<F: PrimeField>(elements: &[F]) -> Vec<u8> {
+    elements.iter().flat_map(|x| x.into_bigint().to_bytes_be()).collect()
+}
+
+// The one derivation of a round challenge, shared by every prover and verifier: absorbs the claim going into
+// the round and the round polynomial's evaluations under their own labels, then squeezes under a third
+pub fn next_challenge<F: PrimeField, H: HashTrait, T: TranscriptTrait<F>>(transcript: &mut T, round_poly: &[F], claimed: F) -> F {
+    transcript.absorb_labeled(b"claimed_sum", &field_bytes(&[claimed]));
+    transcript.absorb_labeled(b"round_poly", &field_bytes(round_poly));
+    transcript.absorb_labeled(b"challenge", &[]);
+    transcript.challenge()
+}
+
 pub fn add_data_to_transcript <F: PrimeField, H: HashTrait, T: TranscriptTrait<F>> (data: &Vec<F>, transcript: &mut T) -> F {
-    let mut bytes = vec![];
-    data.iter().for_each(|x| {
-        bytes.extend(x.into_bigint().to_bytes_be())
-    });
-    transcript.absorb(&bytes);
-    let squeezed = transcript.squeeze();
-    let squeezed_bytes = squeezed.into_bigint().to_bytes_be();
-    let challenge = F::from_be_bytes_mod_order(&squeezed_bytes);
-    return challenge;
+    transcript.absorb_field_slice(data);
+    transcript.challenge()
 }
 
 
@@ -147,6 +239,7 @@ mod tests{
       use ark_bn254::Fq;
       use sha3::{Keccak256, Digest};
       use transcript::transcript::KeccakWrapper;
+      use multilinear::composite::{Composite, OP};
   
     use multilinear::multilinear::MultivariatePoly;
 
@@ -242,4 +335,277 @@ mod tests{
             composite.evaluate(&challenges.iter().map(|x| Some(x.clone())).collect())
         );
     }
-}
\ No newline at end of file
+
+    fn example_composite() -> Composite<Fq> {
+        // (2a + 3b) * (4b + 7ab) + (2ab + 3b + 6a)
+        let poly_a = [0, 3, 2, 5].iter().map(|x| Fq::from(*x)).collect();
+        let poly_b = [0, 4, 0, 11].iter().map(|x| Fq::from(*x)).collect();
+        let poly_c = [0, 3, 6, 11].iter().map(|x| Fq::from(*x)).collect();
+        Composite::new(&vec![poly_a, poly_b, poly_c], vec![OP::MUL, OP::ADD])
+    }
+
+    #[test]
+    fn test_prover_and_verifier_challenges_match() {
+        let composite = example_composite();
+        let mut round_polys: Vec<DensePolynomial<Fq>> = vec![];
+        let mut prover_challenges = vec![];
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let initial_sum = generate_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut transcript, &mut round_polys, &mut prover_challenges);
+
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let (sum, verifier_challenges) = verify_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(initial_sum, &round_polys, &mut transcript);
+
+        assert_eq!(prover_challenges, verifier_challenges);
+        assert_eq!(sum, composite.evaluate(&verifier_challenges.iter().map(|x| Some(*x)).collect()));
+    }
+
+    #[test]
+    fn test_next_challenge_agrees_for_prover_and_verifier() {
+        let a = MultivariatePoly::new((1..=16).map(|x| Fq::from(x as u64)).collect(), 4);
+        let b = MultivariatePoly::new((1..=16).map(|x| Fq::from((x * x + 3) as u64)).collect(), 4);
+        let composite = Composite::new(&vec![a.coeffs, b.coeffs], vec![OP::MUL]);
+
+        let mut round_polys: Vec<DensePolynomial<Fq>> = vec![];
+        let mut prover_challenges = vec![];
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let initial_sum = generate_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut transcript, &mut round_polys, &mut prover_challenges);
+        assert_eq!(prover_challenges.len(), 4);
+
+        // a verifier deriving each challenge itself lands on the prover's whole sequence
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let mut sum = initial_sum;
+        let mut verifier_challenges = vec![];
+        for round_poly in round_polys.iter() {
+            let challenge = next_challenge::<Fq, KeccakWrapper, _>(&mut transcript, &round_poly.coefficients, sum);
+            sum = round_poly_to_dense(&round_poly.coefficients).evaluate(challenge);
+            verifier_challenges.push(challenge);
+        }
+        assert_eq!(verifier_challenges, prover_challenges);
+        assert_eq!(sum, composite.evaluate(&prover_challenges.iter().map(|x| Some(*x)).collect()));
+
+        // as does every verifier entry point
+        let proof = SumCheckProof { claimed_sum: initial_sum, degree: composite.degree(), round_polys, final_evaluation: sum };
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let (_, challenges) = verify_partial_proof::<Fq, KeccakWrapper, _>(initial_sum, &proof.round_polys, &mut transcript);
+        assert_eq!(challenges, prover_challenges);
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let oracle = |point: &[Fq]| {
+            assert_eq!(point, &prover_challenges[..]);
+            composite.evaluate(&point.iter().map(|x| Some(*x)).collect())
+        };
+        assert_eq!(verify_with_oracle::<Fq, KeccakWrapper, _, _>(&proof, &mut transcript, oracle), Ok(()));
+    }
+
+    #[test]
+    fn test_polynomial_constant_in_last_variable() {
+        // f(x0, x1) = 3 + 4·x0, so the last round polynomial is the constant f(r0, ·)
+        let f: Vec<Fq> = [3u64, 3, 7, 7].map(Fq::from).to_vec();
+        for composite in [
+            Composite::new(&vec![f.clone()], vec![]),
+            Composite::new(&vec![f.clone(), f.clone()], vec![OP::MUL]),
+        ] {
+            let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+            let proof = generate_proof::<Fq, KeccakWrapper, _>(&composite, &mut transcript);
+
+            let last = proof.round_polys.last().unwrap();
+            assert!(last.coefficients.iter().all(|&eval| eval == last.coefficients[0]));
+            let last_dense = round_poly_to_dense(&last.coefficients);
+            assert_eq!(last_dense.degree(), 0);
+            assert_eq!(last_dense.evaluate(Fq::from(5)), last.coefficients[0]);
+
+            let oracle = |point: &[Fq]| composite.evaluate(&point.iter().map(|x| Some(*x)).collect());
+            let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+            assert_eq!(verify_with_degree_bound::<Fq, KeccakWrapper, _, _>(&proof, &mut transcript, composite.degree(), oracle), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_labeled_round_framing_replays_prover_challenges() {
+        let composite = example_composite();
+        let mut round_polys: Vec<DensePolynomial<Fq>> = vec![];
+        let mut prover_challenges = vec![];
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let initial_sum = generate_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut transcript, &mut round_polys, &mut prover_challenges);
+
+        // absorbing the labeled claim and round polynomial round by round reproduces the prover's challenges
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let mut sum = initial_sum;
+        let mut replayed = vec![];
+        for round_poly in round_polys.iter() {
+            transcript.absorb_labeled(b"claimed_sum", &field_bytes(&[sum]));
+            transcript.absorb_labeled(b"round_poly", &field_bytes(&round_poly.coefficients));
+            transcript.absorb_labeled(b"challenge", &[]);
+            let challenge = transcript.challenge();
+            sum = round_poly_to_dense(&round_poly.coefficients).evaluate(challenge);
+            replayed.push(challenge);
+        }
+        assert_eq!(replayed, prover_challenges);
+
+        let polys: Vec<Vec<Fq>> = round_polys.iter().map(|p| p.coefficients.clone()).collect();
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let (_, verifier_challenges, _) = verify_partial_proof_2::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(initial_sum, &polys, &mut transcript);
+        assert_eq!(verifier_challenges, prover_challenges);
+    }
+
+    #[test]
+    fn test_tampered_round_poly_changes_challenges() {
+        let composite = example_composite();
+        let mut round_polys: Vec<DensePolynomial<Fq>> = vec![];
+        let mut prover_challenges = vec![];
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let initial_sum = generate_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut transcript, &mut round_polys, &mut prover_challenges);
+
+        // shift weight between p(0) and p(1) so the round sum check still passes
+        round_polys[0].coefficients[0] += Fq::from(1);
+        round_polys[0].coefficients[1] -= Fq::from(1);
+
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let result = std::panic::catch_unwind(move || {
+            verify_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(initial_sum, &round_polys, &mut transcript)
+        });
+
+        // either a later round check fails, or the final claim no longer matches the polynomial
+        if let Ok((sum, verifier_challenges)) = result {
+            assert_ne!(verifier_challenges, prover_challenges);
+            assert_ne!(sum, composite.evaluate(&verifier_challenges.iter().map(|x| Some(*x)).collect()));
+        }
+    }
+
+    #[test]
+    fn test_proof_size_bytes_matches_serialization() {
+        let mut sizes = vec![];
+        for num_vars in 1..=4u32 {
+            let len = 1u64 << num_vars;
+            let poly_a: Vec<Fq> = (0..len).map(|x| Fq::from(x * 3 + 1)).collect();
+            let poly_b: Vec<Fq> = (0..len).map(|x| Fq::from(x * x + 2)).collect();
+            let composite = Composite::new(&vec![poly_a, poly_b], vec![OP::MUL]);
+
+            let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+            let proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut transcript);
+
+            assert_eq!(proof.round_polys.len(), num_vars as usize);
+            assert_eq!(proof.proof_size_bytes(), proof.to_bytes().len());
+            sizes.push(proof.proof_size_bytes());
+        }
+
+        // each extra variable adds one round polynomial of the same size
+        let growth: Vec<usize> = sizes.windows(2).map(|w| w[1] - w[0]).collect();
+        assert!(growth.iter().all(|&g| g == growth[0]));
+        assert_eq!(growth[0], 3 * 32 + 8);
+    }
+
+    #[test]
+    fn test_degree_three_composite() {
+        // a * b * c + d, degree 3 in every variable
+        let poly_a: Vec<Fq> = [1, 3, 2, 5].iter().map(|x| Fq::from(*x)).collect();
+        let poly_b: Vec<Fq> = [0, 4, 7, 11].iter().map(|x| Fq::from(*x)).collect();
+        let poly_c: Vec<Fq> = [2, 3, 6, 11].iter().map(|x| Fq::from(*x)).collect();
+        let poly_d: Vec<Fq> = [5, 0, 1, 9].iter().map(|x| Fq::from(*x)).collect();
+        let composite = Composite::new(&vec![poly_a, poly_b, poly_c, poly_d], vec![OP::MUL, OP::MUL, OP::ADD]);
+
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut transcript);
+        assert_eq!(proof.degree, 3);
+        assert!(proof.round_polys.iter().all(|p| p.coefficients.len() == 4));
+        assert_eq!(proof.claimed_sum, composite.reduce().coeffs.iter().sum());
+
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let (sum, challenges) = verify_partial_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(proof.claimed_sum, &proof.round_polys, &mut transcript);
+        assert_eq!(sum, proof.final_evaluation);
+        assert_eq!(sum, composite.evaluate(&challenges.iter().map(|x| Some(*x)).collect()));
+    }
+
+    #[test]
+    fn test_verify_with_oracle() {
+        let composite = example_composite();
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut transcript);
+        let oracle = |point: &[Fq]| composite.evaluate(&point.iter().map(|x| Some(*x)).collect());
+        let verify = |proof: &SumCheckProof<Fq>| {
+            let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+            verify_with_oracle::<Fq, KeccakWrapper, _, _>(proof, &mut transcript, oracle)
+        };
+        assert_eq!(verify(&proof), Ok(()));
+
+        let mut tampered = proof.clone();
+        tampered.final_evaluation += Fq::from(1);
+        assert_eq!(verify(&tampered), Err(SumCheckError::FinalSumMismatch));
+
+        let mut tampered = proof.clone();
+        tampered.claimed_sum += Fq::from(1);
+        assert_eq!(verify(&tampered), Err(SumCheckError::RoundSumMismatch { round: 0 }));
+
+        // an oracle for a different polynomial
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let result = verify_with_oracle::<Fq, KeccakWrapper, _, _>(&proof, &mut transcript, |point: &[Fq]| oracle(point) + Fq::from(1));
+        assert_eq!(result, Err(SumCheckError::OracleMismatch));
+    }
+
+    #[test]
+    fn test_rejects_prover_chosen_challenges() {
+        // f(x) = 3 + 2x sums to 8 over {0, 1}. Claim 100 instead, and solve for a round polynomial with
+        // g(0) + g(1) = 100 that agrees with f at a challenge r of the prover's choosing
+        let f = MultivariatePoly::new(vec![Fq::from(3u64), Fq::from(5u64)], 1);
+        let claimed_sum = Fq::from(100u64);
+        let r = Fq::from(7u64);
+        let final_evaluation = f.evaluate(&[r]);
+        let g1 = (final_evaluation - claimed_sum * (Fq::from(1u64) - r)) / (Fq::from(2u64) * r - Fq::from(1u64));
+        let g0 = claimed_sum - g1;
+        let forged = SumCheckProof {
+            claimed_sum,
+            degree: 1,
+            round_polys: vec![DensePolynomial { coefficients: vec![g0, g1] }],
+            final_evaluation,
+        };
+        // consistent at r, so a verifier reading the challenge off the proof would accept
+        assert_eq!(round_poly_to_dense(&[g0, g1]).evaluate(r), final_evaluation);
+
+        // the challenge comes from the transcript instead, and the forged line misses f there
+        let mut transcript = Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let result = verify_with_oracle::<Fq, KeccakWrapper, _, _>(&forged, &mut transcript, |point: &[Fq]| f.evaluate(point));
+        assert_eq!(result, Err(SumCheckError::FinalSumMismatch));
+    }
+
+    #[test]
+    fn test_verify_with_degree_bound() {
+        let poly_a: Vec<Fq> = [1, 3, 2, 5].iter().map(|x| Fq::from(*x)).collect();
+        let poly_b: Vec<Fq> = [0, 4, 7, 11].iter().map(|x| Fq::from(*x)).collect();
+        let new_transcript = || Transcript::<KeccakWrapper, Fq>::new(KeccakWrapper { keccak: Keccak256::new() });
+
+        for (op, max_degree) in [(OP::ADD, 1), (OP::MUL, 2)] {
+            let composite = Composite::new(&vec![poly_a.clone(), poly_b.clone()], vec![op]);
+            let proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut new_transcript());
+            assert_eq!(proof.degree, max_degree);
+            let oracle = |point: &[Fq]| composite.evaluate(&point.iter().map(|x| Some(*x)).collect());
+
+            let result = verify_with_degree_bound::<Fq, KeccakWrapper, _, _>(&proof, &mut new_transcript(), max_degree, oracle);
+            assert_eq!(result, Ok(()));
+
+            // keep p(0) and p(1), so the round sum still holds, but add one evaluation too many
+            let mut tampered = proof.clone();
+            let mut evals = tampered.round_polys[0].coefficients[..2].to_vec();
+            evals.extend((2..max_degree + 2).map(|x| Fq::from((x * x + 7) as u64)));
+            tampered.round_polys[0] = DensePolynomial { coefficients: evals };
+            let result = verify_with_degree_bound::<Fq, KeccakWrapper, _, _>(&tampered, &mut new_transcript(), max_degree, oracle);
+            assert_eq!(result, Err(SumCheckError::RoundDegreeTooHigh { round: 0, degree: max_degree + 1, max_degree }));
+        }
+
+        // a product-of-two proof doesn't pass as multilinear
+        let composite = Composite::new(&vec![poly_a, poly_b], vec![OP::MUL]);
+        let proof = generate_proof::<Fq, KeccakWrapper, Transcript<KeccakWrapper, Fq>>(&composite, &mut new_transcript());
+        let oracle = |point: &[Fq]| composite.evaluate(&point.iter().map(|x| Some(*x)).collect());
+        let result = verify_with_degree_bound::<Fq, KeccakWrapper, _, _>(&proof, &mut new_transcript(), 1, oracle);
+        assert_eq!(result, Err(SumCheckError::RoundDegreeTooHigh { round: 0, degree: 2, max_degree: 1 }));
+    }
+
+    #[test]
+    fn test_round_poly_to_dense() {
+        // 3 + 2X + 5X^2 at 0, 1, 2
+        let evals = [Fq::from(3), Fq::from(10), Fq::from(27)];
+        let dense = round_poly_to_dense(&evals);
+        assert_eq!(dense.coefficients, vec![Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let points: Vec<(Fq, Fq)> = evals.iter().enumerate().map(|(x, &y)| (Fq::from(x as u64), y)).collect();
+        assert_eq!(dense.coefficients, DensePolynomial::interpolate(&points).coefficients);
+    }
+}
@@ -0,0 +1,175 @@
+use ark_ff::PrimeField;
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use transcript::transcript::{HashTrait, KeccakWrapper, TranscriptTrait};
+
+pub struct Sha256Wrapper {
+    pub sha256: Sha256,
+}
+
+impl HashTrait for Sha256Wrapper {
+    fn append(&mut self, data: &[u8]) {
+        self.sha256.update(data);
+    }
+
+    fn generate_hash(&self) -> Vec<u8> {
+        self.sha256.clone().finalize().to_vec()
+    }
+}
+
+// Transcript over any `HashTrait` hash, Keccak256 unless another is plugged in, that folds every squeezed
+// hash back into the state, so consecutive challenges differ even without new data being appended
+pub struct Transcript<H: HashTrait = KeccakWrapper> {
+    hasher: H,
+}
+
+impl Transcript {
+    // Create a new Keccak256 Transcript
+    pub fn new() -> Self {
+        Self::with_hasher(KeccakWrapper { keccak: Keccak256::new() })
+    }
+}
+
+impl<H: HashTrait> Transcript<H> {
+    pub fn with_hasher(hasher: H) -> Self {
+        Self { hasher }
+    }
+
+    // Append data to the transcript
+    pub fn append(&mut self, new_data: &[u8]) {
+        self.hasher.append(new_data);
+    }
+
+    // Sample a challenge (the hash of everything so far) and re-absorb it
+    fn sample_challenge(&mut self) -> Vec<u8> {
+        let result_hash = self.hasher.generate_hash();
+        self.hasher.append(&result_hash);
+        result_hash
+    }
+
+    // Sample a field element from the transcript
+    pub fn sample_field_element<F: PrimeField>(&mut self) -> F {
+        let challenge = self.sample_challenge();
+        F::from_be_bytes_mod_order(&challenge)
+    }
+
+    // Sample multiple field elements from the transcript
+    pub fn sample_n_field_elements<F: PrimeField>(&mut self, n: usize) -> Vec<F> {
+        (0..n).map(|_| self.sample_field_element::<F>()).collect()
+    }
+}
+
+impl Default for Transcript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField, H: HashTrait> TranscriptTrait<F> for Transcript<H> {
+    fn absorb(&mut self, data: &[u8]) {
+        self.append(data);
+    }
+
+    fn squeeze(&mut self) -> F {
+        self.sample_field_element()
+    }
+
+    fn generate_challenge(&mut self) -> F {
+        self.sample_field_element()
+    }
+
+    // every squeeze already re-absorbs its hash, so there's nothing to fold in between
+    fn squeeze_n(&mut self, n: usize) -> Vec<F> {
+        self.sample_n_field_elements(n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sumcheck::{generate_partial_proof, generate_proof, verify_partial_proof, verify_with_oracle};
+    use ark_bn254::Fq;
+    use multilinear::composite::{Composite, OP};
+    use prime_polynomail::DensePolynomial;
+    use transcript::transcript::{KeccakWrapper, Transcript as HashTranscript};
+
+    #[test]
+    fn test_trait_squeeze_matches_sample_field_element() {
+        let mut by_trait = Transcript::new();
+        let mut by_method = Transcript::new();
+
+        TranscriptTrait::<Fq>::absorb(&mut by_trait, b"round data");
+        by_method.append(b"round data");
+
+        for _ in 0..3 {
+            let expected: Fq = by_method.sample_field_element();
+            assert_eq!(TranscriptTrait::<Fq>::squeeze(&mut by_trait), expected);
+        }
+    }
+
+    #[test]
+    fn test_sumcheck_with_concrete_transcript() {
+        // (2a + 3b) * (4b + 7ab) + (2ab + 3b + 6a)
+        let poly_a = [0, 3, 2, 5].iter().map(|x| Fq::from(*x)).collect();
+        let poly_b = [0, 4, 0, 11].iter().map(|x| Fq::from(*x)).collect();
+        let poly_c = [0, 3, 6, 11].iter().map(|x| Fq::from(*x)).collect();
+        let composite = Composite::new(&vec![poly_a, poly_b, poly_c], vec![OP::MUL, OP::ADD]);
+
+        let mut round_polys: Vec<DensePolynomial<Fq>> = vec![];
+        let mut challenges = vec![];
+        let mut transcript = Transcript::new();
+        let initial_sum = generate_partial_proof::<Fq, KeccakWrapper, Transcript>(&composite, &mut transcript, &mut round_polys, &mut challenges);
+
+        let mut transcript = Transcript::new();
+        let (sum, verifier_challenges) = verify_partial_proof::<Fq, KeccakWrapper, Transcript>(initial_sum, &round_polys, &mut transcript);
+
+        assert_eq!(verifier_challenges, challenges);
+        assert_eq!(sum, composite.evaluate(&challenges.iter().map(|x| Some(*x)).collect()));
+    }
+
+    #[test]
+    fn test_pluggable_hash() {
+        let composite = Composite::new(
+            &vec![[0, 3, 2, 5].map(Fq::from).to_vec(), [0, 4, 0, 11].map(Fq::from).to_vec()],
+            vec![OP::MUL],
+        );
+        let oracle = |point: &[Fq]| composite.evaluate(&point.iter().map(|x| Some(*x)).collect());
+        let sha256 = || Transcript::with_hasher(Sha256Wrapper { sha256: Sha256::new() });
+
+        // prover and verifier on the same hash agree, whichever hash it is
+        let keccak_proof = generate_proof::<Fq, KeccakWrapper, _>(&composite, &mut Transcript::new());
+        assert_eq!(verify_with_oracle::<Fq, KeccakWrapper, _, _>(&keccak_proof, &mut Transcript::new(), oracle), Ok(()));
+        let sha256_proof = generate_proof::<Fq, Sha256Wrapper, _>(&composite, &mut sha256());
+        assert_eq!(verify_with_oracle::<Fq, Sha256Wrapper, _, _>(&sha256_proof, &mut sha256(), oracle), Ok(()));
+
+        // a verifier on the other hash draws different challenges and rejects
+        assert!(verify_with_oracle::<Fq, Sha256Wrapper, _, _>(&keccak_proof, &mut sha256(), oracle).is_err());
+        assert!(verify_with_oracle::<Fq, KeccakWrapper, _, _>(&sha256_proof, &mut Transcript::new(), oracle).is_err());
+    }
+
+    // This transcript and `transcript::transcript::Transcript` both hash everything absorbed with Keccak256
+    // and re-absorb every squeezed digest, so they draw the same challenges, consecutive squeezes included
+    #[test]
+    fn test_squeeze_matches_hash_transcript() {
+        let mut reabsorbing = Transcript::new();
+        let mut hash_transcript = HashTranscript::<KeccakWrapper, Fq>::new(KeccakWrapper {
+            keccak: Keccak256::new(),
+        });
+
+        for data in [b"claimed sum".as_slice(), b"round poly 0"] {
+            TranscriptTrait::<Fq>::absorb(&mut reabsorbing, data);
+            hash_transcript.absorb(data);
+        }
+
+        let first = TranscriptTrait::<Fq>::squeeze(&mut reabsorbing);
+        assert_eq!(first, hash_transcript.squeeze());
+
+        let second = TranscriptTrait::<Fq>::squeeze(&mut reabsorbing);
+        assert_ne!(second, first);
+        assert_eq!(hash_transcript.squeeze(), second);
+
+        TranscriptTrait::<Fq>::absorb(&mut reabsorbing, b"round poly 1");
+        hash_transcript.absorb(b"round poly 1");
+        assert_eq!(TranscriptTrait::<Fq>::squeeze(&mut reabsorbing), hash_transcript.squeeze());
+    }
+}
@@ -24,6 +24,7 @@ impl<K: HashTrait, F: PrimeField> Transcript<K, F> {
     // Squeeze will return a field element
     pub fn squeeze(&mut self) -> F {
         let hash_output = self.hash_function.generate_hash();
+        self.hash_function.append(&hash_output);
         F::from_be_bytes_mod_order(&hash_output)
     }
 
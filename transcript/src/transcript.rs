@@ -1,4 +1,4 @@
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
 use sha3::{Keccak256, Digest};
 use std::marker::PhantomData;
 
@@ -7,6 +7,46 @@ pub trait  TranscriptTrait<F: PrimeField> {
     fn absorb(&mut self, data: &[u8]);
     fn squeeze(&mut self) -> F;
     fn generate_challenge(&mut self) -> F;
+
+    // `squeeze` already reduces the hash into the field, so the challenge needs no round trip through bytes
+    fn challenge(&mut self) -> F {
+        self.squeeze()
+    }
+
+    // `n` challenges, each draw absorbed before the next so they are independent even for a transcript
+    // whose `squeeze` alone would repeat itself
+    fn squeeze_n(&mut self, n: usize) -> Vec<F> {
+        let mut draws: Vec<F> = Vec::with_capacity(n);
+        for i in 0..n {
+            if i > 0 {
+                self.absorb_field(&draws[i - 1]);
+            }
+            draws.push(self.squeeze());
+        }
+        draws
+    }
+
+    // Absorbs `label.len()` as a big-endian u64, the label, then the data, so messages with equal bytes but
+    // different meanings can't be confused, and neither can where a label ends and its data begins
+    fn absorb_labeled(&mut self, label: &[u8], data: &[u8]) {
+        let mut bytes = (label.len() as u64).to_be_bytes().to_vec();
+        bytes.extend(label);
+        bytes.extend(data);
+        self.absorb(&bytes);
+    }
+
+    // The element's canonical big-endian bytes
+    fn absorb_field(&mut self, value: &F) {
+        self.absorb(&value.into_bigint().to_bytes_be());
+    }
+
+    // The element count as a big-endian u64, then every element's big-endian bytes, so slices split
+    // differently across calls don't absorb the same bytes
+    fn absorb_field_slice(&mut self, values: &[F]) {
+        let mut bytes = (values.len() as u64).to_be_bytes().to_vec();
+        values.iter().for_each(|x| bytes.extend(x.into_bigint().to_bytes_be()));
+        self.absorb(&bytes);
+    }
 }
 pub struct Transcript<K: HashTrait, F: PrimeField> {
     _field: PhantomData<F>, // Placeholder to hold the field even if we are not using it
@@ -26,9 +66,11 @@ impl<K: HashTrait, F: PrimeField> Transcript<K, F> {
         self.hash_function.append(data);
     }
 
-    // Squeeze will return a field element
+    // Squeeze will return a field element, re-absorbing the digest so the next squeeze differs even
+    // without new data
     pub fn squeeze(&mut self) -> F {
         let hash_output = self.hash_function.generate_hash();
+        self.hash_function.append(&hash_output);
         F::from_be_bytes_mod_order(&hash_output)
     }
 
@@ -36,6 +78,11 @@ impl<K: HashTrait, F: PrimeField> Transcript<K, F> {
     pub fn generate_challenge(&mut self) -> F {
         self.squeeze()
     }
+
+    // `n` challenges; every squeeze folds its hash output back into the state, so they're independent
+    pub fn squeeze_n(&mut self, n: usize) -> Vec<F> {
+        (0..n).map(|_| self.squeeze()).collect()
+    }
 }
 
 // A vector is a growable array, but a slice is a fixed-size array you can only push to a specific index
@@ -64,13 +111,16 @@ impl<F: PrimeField> TranscriptTrait<F> for Transcript<KeccakWrapper, F> {
     }
 
     fn squeeze(&mut self) -> F {
-        let hash_output = self.hash_function.generate_hash();
-        F::from_be_bytes_mod_order(&hash_output)
+        Transcript::squeeze(self)
     }
 
     fn generate_challenge(&mut self) -> F {
         self.squeeze()
     }
+
+    fn squeeze_n(&mut self, n: usize) -> Vec<F> {
+        Transcript::squeeze_n(self, n)
+    }
 }
 
 fn main() {
@@ -95,6 +145,97 @@ mod test {
         println!("output: {:?}", output);
     }
 
+    #[test]
+    fn test_challenge_matches_byte_round_trip() {
+        use ark_ff::BigInteger;
+
+        let new_transcript = || Transcript::<KeccakWrapper, Fr>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let mut direct = new_transcript();
+        let mut round_trip = new_transcript();
+
+        for data in [b"first".as_slice(), b"second", b"", b"third"] {
+            TranscriptTrait::absorb(&mut direct, data);
+            TranscriptTrait::absorb(&mut round_trip, data);
+            let expected = Fr::from_be_bytes_mod_order(&TranscriptTrait::squeeze(&mut round_trip).into_bigint().to_bytes_be());
+            assert_eq!(direct.challenge(), expected);
+        }
+    }
+
+    #[test]
+    fn test_absorb_labeled_separates_messages() {
+        let challenge_after = |messages: &[(&[u8], &[u8])]| {
+            let mut transcript = Transcript::<KeccakWrapper, Fr>::new(KeccakWrapper { keccak: Keccak256::new() });
+            for (label, data) in messages {
+                transcript.absorb_labeled(label, data);
+            }
+            transcript.challenge()
+        };
+
+        let sum: (&[u8], &[u8]) = (b"claimed_sum", b"\x01\x02");
+        let poly: (&[u8], &[u8]) = (b"round_poly", b"\x03\x04");
+        assert_eq!(challenge_after(&[sum, poly]), challenge_after(&[sum, poly]));
+        assert_ne!(challenge_after(&[sum, poly]), challenge_after(&[poly, sum]));
+
+        // the same data under another label, and the same bytes split differently between label and data
+        assert_ne!(challenge_after(&[(b"claimed_sum", b"x")]), challenge_after(&[(b"round_poly", b"x")]));
+        assert_ne!(challenge_after(&[(b"ab", b"c")]), challenge_after(&[(b"a", b"bc")]));
+    }
+
+    #[test]
+    fn test_absorb_field_matches_manual_bytes() {
+        let new_transcript = || Transcript::<KeccakWrapper, Fr>::new(KeccakWrapper { keccak: Keccak256::new() });
+        let values = [Fr::from(3u64), -Fr::from(1u64), Fr::from(u64::MAX)];
+
+        let mut helper = new_transcript();
+        let mut manual = new_transcript();
+        helper.absorb_field(&values[1]);
+        TranscriptTrait::absorb(&mut manual, &values[1].into_bigint().to_bytes_be());
+        assert_eq!(helper.challenge(), manual.challenge());
+
+        helper.absorb_field_slice(&values);
+        let mut bytes = 3u64.to_be_bytes().to_vec();
+        values.iter().for_each(|x| bytes.extend(x.into_bigint().to_bytes_be()));
+        TranscriptTrait::absorb(&mut manual, &bytes);
+        assert_eq!(helper.challenge(), manual.challenge());
+
+        // the length prefix keeps [a, b] + [c] apart from [a] + [b, c]
+        let split = |at: usize| {
+            let mut transcript = new_transcript();
+            transcript.absorb_field_slice(&values[..at]);
+            transcript.absorb_field_slice(&values[at..]);
+            transcript.challenge()
+        };
+        assert_ne!(split(1), split(2));
+    }
+
+    #[test]
+    fn test_consecutive_squeezes_differ() {
+        let mut transcript = Transcript::<KeccakWrapper, Fr>::new(KeccakWrapper { keccak: Keccak256::new() });
+        transcript.absorb(b"round data");
+        let first = transcript.squeeze();
+        let second = transcript.squeeze();
+        assert_ne!(first, second);
+        assert_ne!(TranscriptTrait::squeeze(&mut transcript), second);
+    }
+
+    #[test]
+    fn test_squeeze_n_draws_are_distinct() {
+        let mut transcript = Transcript::<KeccakWrapper, Fr>::new(KeccakWrapper { keccak: Keccak256::new() });
+        transcript.absorb(b"round data");
+        let mut single = Transcript::<KeccakWrapper, Fr>::new(KeccakWrapper { keccak: Keccak256::new() });
+        single.absorb(b"round data");
+
+        let draws = transcript.squeeze_n(8);
+        assert_eq!(draws.len(), 8);
+        assert_eq!(draws[0], single.squeeze());
+        for i in 0..draws.len() {
+            for j in i + 1..draws.len() {
+                assert_ne!(draws[i], draws[j]);
+            }
+        }
+        assert_eq!(TranscriptTrait::squeeze_n(&mut single, 0), vec![]);
+    }
+
     #[test]
     fn test_fiat_shamir() {
         let mut transcript = Transcript::<KeccakWrapper, Fr>::new(KeccakWrapper {